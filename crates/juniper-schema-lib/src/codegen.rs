@@ -3,6 +3,8 @@ use std::path::PathBuf;
 
 use crate::CodegenError;
 use crate::ContextType;
+use crate::SchemaFile;
+use crate::naming::RenameStrategy;
 use crate::schema_info::SchemaInfo;
 
 enum MapperToken {
@@ -49,14 +51,15 @@ impl Codegen {
             let (impl_methods, trait_methods) = obj_type.fields.iter().fold(
                 (vec![], vec![]),
                 |(mut impl_methods, mut trait_methods), field| {
-                    let impl_method_name = syn::Ident::new(
+                    let impl_method_name = self.options.rename.field_ident(
                         &field.name,
                         span.clone(),
                     );
                     let resolver_method_name = syn::Ident::new(
-                        format!("resolve_{}", &field.name).as_str(),
+                        format!("resolve_{}", self.options.rename.apply(&field.name)).as_str(),
                         span.clone(),
                     );
+                    let graphql_name = syn::LitStr::new(field.name.as_str(), span.clone());
 
                     let mut impl_method_params = vec![
                         quote::quote! { &self },
@@ -68,8 +71,8 @@ impl Codegen {
                     let mut resolver_args = vec![];
 
                     // If a context type is specified, use it
-                    match &self.options.context_type {
-                        Some(ContextType::Global(type_ident)) => {
+                    match self.options.context_type.as_ref().map(ContextType::juniper_context_type) {
+                        Some(type_ident) => {
                             impl_method_params.push(quote::quote! {
                                 ctx: &#type_ident
                             });
@@ -90,7 +93,16 @@ impl Codegen {
                         /* nullable = */ true,
                     );
 
+                    // Schema descriptions become doc comments, which juniper
+                    // also picks up as the field's description
+                    let doc_attr = field.description.as_ref().map(|description| {
+                        let description = syn::LitStr::new(description.as_str(), span.clone());
+                        quote::quote! { #[doc = #description] }
+                    });
+
                     impl_methods.push(quote::quote! {
+                        #doc_attr
+                        #[graphql(name=#graphql_name)]
                         pub async fn #impl_method_name(#(#impl_method_params),*) -> #return_type {
                             // Delegate to resolver trait method
                             self.#resolver_method_name(#(#resolver_args),*).await
@@ -98,6 +110,7 @@ impl Codegen {
                     });
 
                     trait_methods.push(quote::quote! {
+                        #doc_attr
                         async fn #resolver_method_name(#(#trait_method_params),*) -> #return_type;
                     });
 
@@ -106,7 +119,7 @@ impl Codegen {
             );
 
             let mut juniper_attr_macro_args = vec![];
-            if let Some(ContextType::Global(type_ident)) = &self.options.context_type {
+            if let Some(type_ident) = self.options.context_type.as_ref().map(ContextType::juniper_context_type) {
                 juniper_attr_macro_args.push(quote::quote! {
                     Context = #type_ident
                 });
@@ -252,11 +265,13 @@ impl Codegen {
 pub struct CodegenOptions {
     pub context_type: Option<ContextType>,
     graphql_to_rust_type_map: Option<HashMap<String, String>>,
+    pub rename: RenameStrategy,
 }
 impl syn::parse::Parse for CodegenOptions {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut context_type = None::<ContextType>;
         let mut graphql_to_rust_type_map = HashMap::new();
+        let mut rename = None::<RenameStrategy>;
 
         // Don't have an opinion on which arrow is used for arrow syntax except
         // that the same arrow is used consistently. Helps when you can't
@@ -273,6 +288,16 @@ impl syn::parse::Parse for CodegenOptions {
                             "`context_type` specified more than once!",
                         ));
                     }
+                    // Only from_file3!() knows how to hand resolvers a
+                    // per-type sub-context
+                    if input.peek(syn::token::Brace) {
+                        return Err(syn::parse::Error::new(
+                            input.span(),
+                            "Per-type context types (`context_type: { default: ..., ... }`) \
+                            are only supported by `juniper_schema::from_file3!()`. Specify a \
+                            single context type here instead (e.g. `context_type: MyContext`).",
+                        ));
+                    }
                     let _ = context_type.insert(
                         ContextType::Global(input.parse::<syn::Type>()?)
                     );
@@ -315,6 +340,17 @@ impl syn::parse::Parse for CodegenOptions {
                     }
                 },
 
+                "rename" => {
+                    let _ = input.parse::<syn::Token![:]>()?;
+                    if let Some(_) = rename {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "`rename` specified more than once!",
+                        ));
+                    }
+                    let _ = rename.insert(input.parse::<RenameStrategy>()?);
+                },
+
                 other => {
                     return Err(syn::parse::Error::new(
                         opt_key.span(),
@@ -338,6 +374,7 @@ impl syn::parse::Parse for CodegenOptions {
         Ok(CodegenOptions {
             context_type,
             graphql_to_rust_type_map,
+            rename: rename.unwrap_or_default(),
         })
     }
 }
@@ -346,6 +383,7 @@ impl Default for CodegenOptions {
         CodegenOptions {
             context_type: None,
             graphql_to_rust_type_map: None,
+            rename: RenameStrategy::default(),
         }
     }
 }
@@ -392,6 +430,7 @@ impl CodegenOptions {
 pub struct CodegenFromFile {
     options: CodegenOptions,
     schema_path: PathBuf,
+    schema_path_name: String,
     schema_path_span: proc_macro2::Span,
 }
 impl syn::parse::Parse for CodegenFromFile {
@@ -428,6 +467,7 @@ impl CodegenFromFile {
         CodegenFromFile {
             options,
             schema_path,
+            schema_path_name: schema_relative_path.clone(),
             schema_path_span,
         }
     }
@@ -436,6 +476,13 @@ impl CodegenFromFile {
         let schema_str = std::fs::read_to_string(&self.schema_path).map_err(|e| {
             CodegenError::IoError(e, self.schema_path_span)
         })?;
-        Codegen::new(schema_str, self.options)
+        let schema_file = SchemaFile {
+            path: self.schema_path_name,
+            source: schema_str,
+            span: self.schema_path_span,
+        };
+        Codegen::new(schema_file.source.clone(), self.options).map_err(
+            |e| e.in_schema_file(&schema_file)
+        )
     }
 }
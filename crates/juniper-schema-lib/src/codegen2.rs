@@ -4,12 +4,14 @@ use std::path::PathBuf;
 use super::CodegenError;
 use super::codegen::CodegenOptions;
 use super::ContextType;
+use super::SchemaFile;
 use super::schema_info::SchemaInfo;
 
 pub struct SchemaFromFile2 {
     options: CodegenOptions,
     root_node_ident: syn::Ident,
     schema_path: PathBuf,
+    schema_path_name: String,
     schema_path_span: proc_macro2::Span,
 }
 impl syn::parse::Parse for SchemaFromFile2 {
@@ -42,6 +44,7 @@ impl syn::parse::Parse for SchemaFromFile2 {
             options,
             root_node_ident,
             schema_path,
+            schema_path_name: schema_path_litstr.value(),
             schema_path_span,
         })
     }
@@ -51,7 +54,14 @@ impl SchemaFromFile2 {
         let schema_str = std::fs::read_to_string(&self.schema_path).map_err(|e| {
             CodegenError::IoError(e, self.schema_path_span)
         })?;
-        Ok(Codegen::new(self.root_node_ident, schema_str, self.options)?)
+        let schema_file = SchemaFile {
+            path: self.schema_path_name,
+            source: schema_str,
+            span: self.schema_path_span,
+        };
+        Ok(Codegen::new(self.root_node_ident, schema_file.source.clone(), self.options).map_err(
+            |e| e.in_schema_file(&schema_file)
+        )?)
     }
 }
 
@@ -181,10 +191,9 @@ impl Codegen {
      */
     fn generate_object_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
         let context_param = self.options.context_type.as_ref().map(|ctx_type| {
-            match ctx_type {
-                ContextType::Global(type_ident) => quote::quote! {
-                    context=#type_ident
-                }
+            let type_ident = ctx_type.juniper_context_type();
+            quote::quote! {
+                context=#type_ident
             }
         });
 
@@ -208,18 +217,19 @@ impl Codegen {
                 let default_span = proc_macro2::Span::call_site();
                 let resolver_methods = graphql_obj_type.fields.iter().map(
                     |field| {
-                        let method_name_ident = syn::Ident::new(
+                        let method_name_ident = self.options.rename.field_ident(
                             &field.name,
                             default_span,
                         );
+                        let graphql_name = syn::LitStr::new(field.name.as_str(), default_span);
 
                         // TODO: Handle field-params
                         let mut wrapper_method_params = vec![
                             quote::quote! { &self },
                         ];
                         let mut impl_method_args = vec![];
-                        match &self.options.context_type {
-                            Some(ContextType::Global(type_ident)) => {
+                        match self.options.context_type.as_ref().map(ContextType::juniper_context_type) {
+                            Some(type_ident) => {
                                 wrapper_method_params.push(quote::quote! {
                                     ctx: &#type_ident
                                 });
@@ -234,6 +244,7 @@ impl Codegen {
                         //let return_type_ident =
 
                         quote::quote! {
+                            #[graphql(name=#graphql_name)]
                             pub async fn #method_name_ident(#(#wrapper_method_params),*) -> String {
                                 // TODO: If dealing with a GraphQL object-typed field, need to wrap
                                 //       this in FIELDWrapper::new()
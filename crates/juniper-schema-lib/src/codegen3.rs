@@ -1,16 +1,20 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 
 use super::CodegenError;
 use super::ContextType;
+use super::SchemaFile;
+use super::naming::RenameStrategy;
 use super::schema_info::SchemaInfo;
 
 pub struct SchemaFromFile3 {
     options: CodegenOptions,
     root_node_ident: syn::Ident,
     schema_path: PathBuf,
+    schema_path_name: String,
     schema_path_span: proc_macro2::Span,
 }
 impl syn::parse::Parse for SchemaFromFile3 {
@@ -51,6 +55,7 @@ impl syn::parse::Parse for SchemaFromFile3 {
             options,
             root_node_ident,
             schema_path,
+            schema_path_name: schema_path_litstr.value(),
             schema_path_span,
         })
     }
@@ -60,7 +65,12 @@ impl SchemaFromFile3 {
         let schema_str = std::fs::read_to_string(&self.schema_path).map_err(|e| {
             CodegenError::IoError(e, self.schema_path_span)
         })?;
-        Ok(Codegen::new(self.root_node_ident, schema_str, self.options)?)
+        let schema_file = SchemaFile {
+            path: self.schema_path_name,
+            source: schema_str,
+            span: self.schema_path_span,
+        };
+        Ok(Codegen::new(self.root_node_ident, schema_file, self.options)?)
     }
 }
 
@@ -68,21 +78,27 @@ pub struct Codegen {
     options: CodegenOptions,
     root_node_ident: syn::Ident,
     schema_info: SchemaInfo<'static>,
+    schema_file: SchemaFile,
 }
 impl Codegen {
     pub fn new(
         root_node_ident: syn::Ident,
-        schema_str: String,
+        schema_file: SchemaFile,
         options: CodegenOptions,
     ) -> Result<Self, CodegenError> {
-        let schema_info = SchemaInfo::parse(schema_str)?;
+        let schema_info = SchemaInfo::parse(schema_file.source.clone()).map_err(
+            |e| e.in_schema_file(&schema_file)
+        )?;
 
-        options.validate(&schema_info)?;
+        options.validate(&schema_info, &schema_file).map_err(
+            |e| e.in_schema_file(&schema_file)
+        )?;
 
         Ok(Codegen {
             options,
             root_node_ident,
             schema_info,
+            schema_file,
         })
     }
 
@@ -108,10 +124,7 @@ impl Codegen {
                         quote::quote! { juniper::ID }
                     } else {
                         let graphql_type_name = String::from(name);
-                        let rust_type = self.options.graphql_type_name_to_rust_type(
-                            &graphql_type_name
-                        );
-                        rust_type.to_token_stream()
+                        self.graphql_type_name_to_rust_type(&graphql_type_name)
                     };
 
                 /*
@@ -165,6 +178,108 @@ impl Codegen {
         }
     }
 
+    /**
+     * Most GraphQL types map to whatever Rust type was specified for them in
+     * CodegenOptions (custom scalars are mapped separately in the `scalars`
+     * option). Input object types are the exception: If the user didn't
+     * map one to their own Rust type, we generate a struct for it (see
+     * generate_input_object_types()) and refer to that instead.
+     */
+    fn graphql_type_name_to_rust_type(
+        &self,
+        graphql_type_name: &String,
+    ) -> proc_macro2::TokenStream {
+        if let Some(rust_type) = self.options.graphql_scalar_name_to_rust_type(graphql_type_name) {
+            rust_type.to_token_stream()
+        } else if self.schema_info.input_obj_types.contains_key(graphql_type_name)
+            && !self.options.has_rust_type(graphql_type_name) {
+            let ident = syn::Ident::new(
+                graphql_type_name.as_str(),
+                proc_macro2::Span::call_site(),
+            );
+            quote::quote! { #ident }
+        } else {
+            self.options.graphql_type_name_to_rust_type(graphql_type_name).to_token_stream()
+        }
+    }
+
+    /**
+     * Unlike object types, input objects don't have any resolvers -- they're
+     * just plain data passed in as field arguments. So rather than asking the
+     * user to write a struct that mirrors the schema, we generate one with
+     * `#[derive(juniper::GraphQLInputObject)]`.
+     *
+     * If the user would rather use their own Rust type for an input object,
+     * they can map it in the `types: {}` block. In that case nothing is
+     * generated here and the mapped type is expected to implement juniper's
+     * input object traits itself (i.e. via `#[derive(GraphQLInputObject)]`).
+     */
+    fn generate_input_object_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let default_span = proc_macro2::Span::call_site();
+        let input_obj_defs = self.schema_info.input_obj_types.iter().filter(
+            |(graphql_input_obj_name, _)| !self.options.has_rust_type(graphql_input_obj_name)
+        ).map(|(graphql_input_obj_name, graphql_input_obj_type)| {
+            let struct_ident = syn::Ident::new(graphql_input_obj_name, default_span);
+            let graphql_input_obj_name_litstr = syn::LitStr::new(
+                graphql_input_obj_name.as_str(),
+                default_span,
+            );
+
+            let fields = graphql_input_obj_type.fields.iter().map(|field| {
+                let field_ident = self.options.rename.field_ident(&field.name, default_span);
+                let field_name_litstr = syn::LitStr::new(field.name.as_str(), default_span);
+                let has_default = has_non_null_default(field);
+                let field_type = self.graphql_type_to_rust_type(
+                    &field.value_type,
+                    &default_span,
+                    /* nullable = */ !has_default,
+                );
+
+                // GraphQLInputObject's derive takes its default as an expression
+                // in a string literal
+                let default_param = field.default_value.as_ref().filter(|_| has_default).map(
+                    |default_value| {
+                        let default_expr = self.generate_default_value(default_value, &field_type);
+                        let default_litstr = syn::LitStr::new(
+                            default_expr.to_string().as_str(),
+                            default_span,
+                        );
+                        quote::quote! { , default=#default_litstr }
+                    }
+                );
+                let description_param = description_litstr(&field.description).map(
+                    |description| quote::quote! { , description=#description }
+                );
+                let doc_attr = doc_attr(&field.description);
+
+                quote::quote! {
+                    #doc_attr
+                    #[graphql(name=#field_name_litstr #default_param #description_param)]
+                    pub #field_ident: #field_type
+                }
+            });
+
+            let description_param = description_litstr(&graphql_input_obj_type.description).map(
+                |description| quote::quote! { , description=#description }
+            );
+            let doc_attr = doc_attr(&graphql_input_obj_type.description);
+
+            quote::quote! {
+                #doc_attr
+                #[derive(juniper::GraphQLInputObject)]
+                #[graphql(name=#graphql_input_obj_name_litstr #description_param)]
+                #[allow(non_snake_case)]
+                pub struct #struct_ident {
+                    #(#fields),*
+                }
+            }
+        });
+
+        Ok(quote::quote! {
+            #(#input_obj_defs)*
+        })
+    }
+
     /**
      * For each GraphQL object type defined in the schema, we expect a
      * corresponding rust type (either named the same or with a mapped name in
@@ -214,17 +329,16 @@ impl Codegen {
      */
     fn generate_object_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
         let context_param = self.options.context_type.as_ref().map(|ctx_type| {
-            match ctx_type {
-                ContextType::Global(type_ident) => quote::quote! {
-                    context=#type_ident
-                }
+            let type_ident = ctx_type.juniper_context_type();
+            quote::quote! {
+                context=#type_ident
             }
         });
 
         let obj_defs = self.schema_info.obj_types.iter().map(
             |(graphql_obj_name, graphql_obj_type)| {
                 let wrapper_ident = self.get_wrapper_type_ident(graphql_obj_name);
-                let rust_type_ident = self.options.graphql_type_name_to_rust_type(
+                let rust_type = self.options.graphql_type_name_to_rust_type(
                     graphql_obj_name
                 );
                 /*
@@ -243,53 +357,137 @@ impl Codegen {
                     proc_macro2::Span::call_site(),
                 );
 
-                let default_span = proc_macro2::Span::call_site();
+                // The schema's Subscription type is just an object type, except
+                // that juniper needs it described with #[graphql_subscription]
+                // and its resolvers return streams.
+                let is_subscription = self.schema_info.schema_def.subscription.as_ref() == Some(
+                    graphql_obj_name
+                );
+
+                let mut resolver_traits = vec![];
                 let resolver_methods = graphql_obj_type.fields.iter().map(
                     |field| {
-                        let method_name_ident = syn::Ident::new(
-                            &field.name,
-                            default_span,
+                        let resolver_method = self.generate_resolver_method(
+                            field,
+                            graphql_obj_name,
+                            is_subscription,
                         );
+                        let ResolverMethod {
+                            method_name_ident,
+                            return_type,
+                            body,
+                            resolver_trait,
+                            ..
+                        } = &resolver_method;
+                        resolver_traits.push(resolver_trait.clone());
+                        let wrapper_method_params = resolver_method.wrapper_method_params();
+                        let graphql_attr = resolver_method.graphql_object_attr();
+                        let doc_attr = doc_attr(&resolver_method.description);
 
-                        // TODO: Handle field-params
-                        let mut wrapper_method_params = vec![
-                            quote::quote! { &self },
-                        ];
-                        let mut impl_method_args = vec![];
-                        match &self.options.context_type {
-                            Some(ContextType::Global(type_ident)) => {
-                                wrapper_method_params.push(quote::quote! {
-                                    ctx: &#type_ident
-                                });
-                                impl_method_args.push(quote::quote! {
-                                    ctx
-                                });
-                            }
-                            None => (),
+                        // Only the signature is spanned on the user's type so
+                        // that the body's braces don't trip `unused_braces` once
+                        // juniper inlines them into its own generated block.
+                        // (Subscription resolvers are always async as far as
+                        // juniper is concerned)
+                        let asyncness = if resolver_method.is_sync && !is_subscription {
+                            None
+                        } else {
+                            Some(quote::quote! { async })
+                        };
+                        let signature = quote::quote_spanned! {rust_type.span()=>
+                            pub #asyncness fn #method_name_ident(#(#wrapper_method_params),*) -> #return_type
                         };
+                        quote::quote! {
+                            #doc_attr
+                            #graphql_attr
+                            #signature {
+                                #body
+                            }
+                        }
+                    }
+                ).collect::<Vec<_>>();
 
-                        // TODO: Map GraphQL object types to wrapper types here
-                        let return_type_ident = self.graphql_type_to_rust_type(
-                            &field.field_type,
-                            &rust_type_ident.span(), // TODO: Delete this, its just to get
-                                                            // things compiling for now
-                            /* nullable = */ true,
-                        );
-                        /*
-                        self.options.graphql_type_name_to_rust_type(
-                            &field.field_type.to_string(),
-                        );
-                        */
+                // Juniper inlines the bodies of #[graphql_object] methods into
+                // its own generated code, so there are no inherent methods on
+                // the wrapper left for the interface impls to delegate to.
+                // Instead each interface impl gets its own copy of the
+                // resolver bodies for the fields declared on that interface.
+                //
+                // The object's field may have a narrower type than the
+                // interface's (e.g. `self: User` for the interface's
+                // `self: Node`), so the object's resolver is used as-is and its
+                // result converted to the interface field's type. Likewise,
+                // any extra (optional) arguments that the object's field has
+                // are passed to its resolver as their default (or None).
+                let interface_impls = graphql_obj_type.implements_interfaces.iter().filter_map(
+                    |interface_name| {
+                        let interface_type = self.schema_info.interface_types.get(interface_name)?;
+                        let interface_trait_ident = self.get_interface_trait_ident(interface_name);
+                        let interface_methods = interface_type.fields.iter().map(|interface_field| {
+                            let obj_field = graphql_obj_type.fields.iter().find(
+                                |field| field.name == interface_field.name
+                            ).unwrap_or(interface_field);
+                            let interface_method = self.generate_resolver_method(
+                                interface_field,
+                                interface_name,
+                                /* is_subscription = */ false,
+                            );
+                            let resolver_method = self.generate_resolver_method(
+                                obj_field,
+                                graphql_obj_name,
+                                /* is_subscription = */ false,
+                            );
+                            let method_name_ident = &resolver_method.method_name_ident;
+                            let return_type = &interface_method.return_type;
+                            let body = &resolver_method.body;
+                            let wrapper_method_params = interface_method.wrapper_method_params();
+
+                            let extra_args = resolver_method.arguments.iter().filter(
+                                |ResolverArgument { graphql_name, .. }| {
+                                    !interface_method.arguments.iter().any(
+                                        |arg| arg.graphql_name.value() == graphql_name.value()
+                                    )
+                                }
+                            ).map(|ResolverArgument { ident, rust_type, default, .. }| {
+                                let value = default.clone().unwrap_or(quote::quote! { None });
+                                quote::quote! { let #ident: #rust_type = #value; }
+                            });
 
-                        quote::quote_spanned! {rust_type_ident.span()=>
-                            pub async fn #method_name_ident(#(#wrapper_method_params),*) -> #return_type_ident {
-                                // TODO: If dealing with a GraphQL object-typed field, need to wrap
-                                //       this in FIELDWrapper::new()
-                                self.impl_.#method_name_ident(#(#impl_method_args),*).await
+                            let resolved_value = if obj_field.field_type == interface_field.field_type {
+                                quote::quote! { #body }
+                            } else {
+                                let converted = self.convert_resolved_value(
+                                    &obj_field.field_type,
+                                    &interface_field.field_type,
+                                    /* from_nullable = */ true,
+                                    /* to_nullable = */ true,
+                                    quote::quote! { resolved },
+                                );
+                                quote::quote! {
+                                    let resolved = { #body };
+                                    resolved.map(|resolved| #converted)
+                                }
+                            };
+
+                            let signature = quote::quote_spanned! {rust_type.span()=>
+                                async fn #method_name_ident(#(#wrapper_method_params),*) -> #return_type
+                            };
+                            quote::quote! {
+                                #signature {
+                                    #(#extra_args)*
+                                    #resolved_value
+                                }
                             }
-                        }
+                        });
+
+                        Some(quote::quote! {
+                            #[juniper::graphql_interface]
+                            impl #interface_trait_ident for #wrapper_ident {
+                                #(#interface_methods)*
+                            }
+                        })
                     }
-                );
+                ).collect::<Vec<_>>();
 
                 let obj_type_name_param = quote::quote! {
                     name=#graphql_obj_type_name_litstr
@@ -302,20 +500,49 @@ impl Codegen {
                     juniper_graphql_attr_params.push(&ctx_param);
                 }
 
+                let description_param = description_litstr(&graphql_obj_type.description).map(
+                    |description| quote::quote! { description=#description }
+                );
+                if let Some(description_param) = &description_param {
+                    juniper_graphql_attr_params.push(description_param);
+                }
+
+                let interface_wrapper_idents = graphql_obj_type.implements_interfaces.iter().map(
+                    |interface_name| self.get_wrapper_type_ident(interface_name)
+                ).collect::<Vec<_>>();
+                let impl_param = quote::quote! {
+                    impl=[#(#interface_wrapper_idents),*]
+                };
+                if !interface_wrapper_idents.is_empty() {
+                    juniper_graphql_attr_params.push(&impl_param);
+                }
+
+                let doc_attr = doc_attr(&graphql_obj_type.description);
+                let juniper_attr_macro = if is_subscription {
+                    quote::quote! { juniper::graphql_subscription }
+                } else {
+                    quote::quote! { juniper::graphql_object }
+                };
+
                 quote::quote! {
+                    #doc_attr
                     struct #wrapper_ident {
-                        impl_: #rust_type_ident,
+                        impl_: #rust_type,
                     }
                     impl #wrapper_ident {
-                        pub fn new(impl_: #rust_type_ident) -> Self {
+                        pub fn new(impl_: #rust_type) -> Self {
                             #wrapper_ident { impl_ }
                         }
                     }
 
-                    #[juniper::graphql_object(#(#juniper_graphql_attr_params),*)]
+                    #[#juniper_attr_macro(#(#juniper_graphql_attr_params),*)]
                     impl #wrapper_ident {
                         #(#resolver_methods)*
                     }
+
+                    #(#interface_impls)*
+
+                    #(#resolver_traits)*
                 }
             }
         );
@@ -325,157 +552,1613 @@ impl Codegen {
         })
     }
 
-    fn generate_root_node_wrapper(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
-        // Identify the Query type
-        // TODO: Eventually it should be acceptable for a schema to specify only one of a Query,
-        //       Mutation, or Subscription type.
-        let query_ident =
-            if let Some(query_type_name) = &self.schema_info.schema_def.query {
-                syn::Ident::new(query_type_name, proc_macro2::Span::call_site())
-            } else {
-                return Err(CodegenError::NoQueryDefinitionFound);
+    /**
+     * Produces the pieces of a wrapper resolver method for a given field: The
+     * method name, its params, the GraphQL-facing return type, and a body that
+     * delegates to the user-defined type's resolver.
+     *
+     * These are returned separately (rather than as a single method) so that
+     * the same resolver can be emitted both inside a #[graphql_object] impl
+     * block and inside the #[graphql_interface] impls for each interface the
+     * object implements.
+     *
+     * Resolvers for fields on the Subscription type return a Stream of the
+     * field's type rather than the field's type itself. The generated resolver
+     * maps each item of the user-defined resolver's stream into the
+     * GraphQL-facing type.
+     */
+    fn generate_resolver_method(
+        &self,
+        field: &graphql_parser::schema::Field<'static, String>,
+        parent_type_name: &String,
+        is_subscription: bool,
+    ) -> ResolverMethod {
+        let default_span = proc_macro2::Span::call_site();
+        // The user-defined resolver (and so the wrapper's resolver too) is
+        // named according to the `rename` option. The GraphQL-facing name is
+        // always passed to juniper explicitly.
+        let method_name_ident = self.options.rename.field_ident(&field.name, default_span);
+        let graphql_name = syn::LitStr::new(field.name.as_str(), default_span);
+
+        let mut receiver_params = vec![
+            quote::quote! { &self },
+        ];
+        let mut impl_method_args = vec![];
+        let mut impl_method_param_types = vec![];
+        if let Some(ctx_type) = &self.options.context_type {
+            let type_ident = ctx_type.juniper_context_type();
+            receiver_params.push(quote::quote! {
+                ctx: &#type_ident
+            });
+
+            // Resolvers on types with their own context type are handed that
+            // sub-context rather than the context juniper executes with.
+            match ctx_type.type_context_type(parent_type_name) {
+                Some(type_context_type) => {
+                    impl_method_args.push(quote::quote! {
+                        <#type_context_type as juniper::FromContext<#type_ident>>::from(ctx)
+                    });
+                    impl_method_param_types.push(quote::quote! { &'a #type_context_type });
+                },
+                None => {
+                    impl_method_args.push(quote::quote! {
+                        ctx
+                    });
+                    impl_method_param_types.push(quote::quote! { &'a #type_ident });
+                },
             };
+        }
+
+        // Field arguments are forwarded to the user-defined resolver in the
+        // same order they're declared in the schema (after the context).
+        //
+        // An argument with a (non-null) default value in the schema is never
+        // absent, so the user-defined resolver receives it as a plain `T`
+        // rather than an `Option<T>`.
+        let arguments = field.arguments.iter().map(|arg| {
+            let has_default = has_non_null_default(arg);
+            let rust_type = self.graphql_type_to_rust_type(
+                &arg.value_type,
+                &default_span,
+                /* nullable = */ !has_default,
+            );
+            let default = arg.default_value.as_ref().filter(|_| has_default).map(
+                |default_value| self.generate_default_value(default_value, &rust_type)
+            );
+            ResolverArgument {
+                ident: self.options.rename.field_ident(&arg.name, default_span),
+                graphql_name: syn::LitStr::new(arg.name.as_str(), default_span),
+                rust_type,
+                default,
+                description: arg.description.clone(),
+            }
+        }).collect::<Vec<_>>();
+        impl_method_args.extend(arguments.iter().map(|ResolverArgument { ident, .. }| {
+            quote::quote! { #ident }
+        }));
+        impl_method_param_types.extend(arguments.iter().map(
+            |ResolverArgument { rust_type, .. }| rust_type.clone()
+        ));
 
-        let query_wrapper_ident = self.get_wrapper_type_ident(
-            &query_ident.to_string()
+        let resolved_type = self.graphql_type_to_wrapper_type(
+            &field.field_type,
+            /* nullable = */ true,
         );
 
-        let root_node_ident = &self.root_node_ident;
-        Ok(quote::quote! {
-            pub struct #root_node_ident;
-            impl #root_node_ident {
-                // TODO: Need to collect the Query type specified in the
-                //      `schema {}` decl inside SchemaInfo and use that type here.
-                //
-                //      Also need to consider that that type may have been remapped to some other
-                //      Rust type in CodegenOptions::rust_types.
-                pub fn new(query: #query_ident) -> juniper::RootNode<
-                    'static,
-                    #query_wrapper_ident,
+        // Sync user-defined resolvers are called without `.await`, and the
+        // generated #[graphql_object] resolver for them is sync as well.
+        let is_sync = self.options.is_sync_resolver(parent_type_name, &field.name);
+        let await_suffix = if is_sync {
+            None
+        } else {
+            Some(quote::quote! { .await })
+        };
+        let rust_type = self.graphql_type_to_rust_type(
+            &field.field_type,
+            &default_span,
+            /* nullable = */ true,
+        );
+        let resolver_trait_ident = self.get_resolver_trait_ident(parent_type_name, &field.name);
+        let (return_type, resolver_impls) = if is_subscription {
+            let wrapped_item = self.wrap_resolved_value(
+                &field.field_type,
+                /* nullable = */ true,
+                quote::quote! { item },
+            );
+            let return_type = quote::quote! {
+                juniper::futures::stream::BoxStream<'static, #resolved_type>
+            };
+            let resolver_impls = quote::quote! {
+                impl<S> #resolver_trait_ident for S
+                    where S: juniper::futures::Stream<Item = #rust_type> + Send + 'static
+                {
+                    type Output = #return_type;
+                    fn resolve(self) -> Self::Output {
+                        Box::pin(juniper::futures::StreamExt::map(self, |item| #wrapped_item))
+                    }
+                }
+            };
+            (return_type, resolver_impls)
+        } else {
+            // User-defined resolvers may return either the field's Rust type
+            // or a Result of it, so the generated resolvers always return a
+            // FieldResult and let juniper report any error as a field error.
+            // (The two impls don't overlap since the field's Rust type is never
+            // a Result.)
+            let wrapped_value = self.wrap_resolved_value(
+                &field.field_type,
+                /* nullable = */ true,
+                quote::quote! { resolved },
+            );
+            let return_type = quote::quote! { juniper::FieldResult<#resolved_type> };
+            let resolver_impls = quote::quote! {
+                impl #resolver_trait_ident for #rust_type {
+                    type Output = #return_type;
+                    fn resolve(self) -> Self::Output {
+                        let resolved = self;
+                        Ok(#wrapped_value)
+                    }
+                }
+                impl<E> #resolver_trait_ident for Result<#rust_type, E>
+                    where E: Into<juniper::FieldError>
+                {
+                    type Output = #return_type;
+                    fn resolve(self) -> Self::Output {
+                        self.map(|resolved| #wrapped_value).map_err(Into::into)
+                    }
+                }
+            };
+            (return_type, resolver_impls)
+        };
 
-                    // TODO: Support Mutations
-                    // TODO: Use "context" Ident from CodegenOptions
-                    juniper::EmptyMutation<Context>,
+        // If the user-defined type is missing the resolver (or it has the wrong
+        // signature), rustc would report it from deep inside the code that
+        // juniper generates for the wrapper. Instead, each wrapper resolver
+        // passes the user-defined resolver's result through a per-field trait
+        // which is only implemented for what the field can resolve to, and
+        // whose diagnostic describes the resolver that the schema requires.
+        //
+        // The params get the same treatment: Rather than calling the resolver
+        // as a method (where a params mismatch is a plain E0061/E0308), the
+        // resolver is called as a function through __ResolverFn, which takes
+        // the function's params as a tuple. The tuple of params that the user's
+        // function actually takes must implement a per-field params trait that
+        // is only implemented for the params the schema requires (and which
+        // reports the same diagnostic).
+        //
+        // A missing resolver falls back to __ResolverFallback (which takes the
+        // right params, but returns a __MissingResolver that doesn't implement
+        // the per-field trait) so that it's reported the same way. Inherent
+        // methods always take precedence over the fallback trait's method.
+        //
+        // (The per-field traits live outside of the wrapper resolver so that
+        // the copies of it that juniper makes all report the exact same error,
+        // which rustc then only shows once.)
+        let parent_rust_type = self.options.graphql_type_name_to_rust_type(parent_type_name);
+        let diagnostic_message = syn::LitStr::new(
+            format!(
+                "type `{}` (mapped from GraphQL `{}`) has no resolver `{}` required by {}:{}",
+                tokens_to_string(parent_rust_type.to_token_stream()),
+                parent_type_name,
+                self.describe_resolver_signature(
+                    &method_name_ident,
+                    parent_type_name,
+                    &arguments,
+                    &rust_type,
+                    is_sync,
+                    is_subscription,
+                ),
+                self.schema_file.path,
+                field.position.line,
+            ).as_str(),
+            default_span,
+        );
+        let diagnostic_note = if is_subscription {
+            "subscription resolvers return a `Stream` of the field's type"
+        } else {
+            "resolvers may also return a `Result<_, E>` where `E: Into<juniper::FieldError>`"
+        };
+        let params_trait_ident = self.get_resolver_params_trait_ident(
+            parent_type_name,
+            &field.name,
+        );
+        let param_idents = impl_method_param_types.iter().enumerate().map(
+            |(idx, _)| syn::Ident::new(format!("__a{}", idx).as_str(), default_span)
+        ).collect::<Vec<_>>();
+        let resolver_trait = quote::quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #[diagnostic::on_unimplemented(
+                message = #diagnostic_message,
+                label = "missing or mistyped resolver",
+                note = #diagnostic_note,
+            )]
+            trait #resolver_trait_ident {
+                type Output;
+                fn resolve(self) -> Self::Output;
+            }
+            #resolver_impls
 
-                    // TODO: Support Subscriptions
-                    // TODO: Use "context" Ident from CodegenOptions
-                    juniper::EmptySubscription<Context>,
-                > {
-                    juniper::RootNode::new(
-                        #query_wrapper_ident::new(query),
-                        juniper::EmptyMutation::new(),
-                        juniper::EmptySubscription::new(),
-                    )
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #[diagnostic::on_unimplemented(
+                message = #diagnostic_message,
+                label = "missing or mistyped resolver",
+            )]
+            trait #params_trait_ident<'a>: Sized {
+                fn params(this: &'a #parent_rust_type, #(#param_idents: #impl_method_param_types),*) -> Self;
+            }
+            impl<'a> #params_trait_ident<'a> for (&'a #parent_rust_type, #(#impl_method_param_types,)*) {
+                fn params(this: &'a #parent_rust_type, #(#param_idents: #impl_method_param_types),*) -> Self {
+                    (this, #(#param_idents,)*)
                 }
-
-                // TODO: Impl other delegators to other relevant methods on
-                //       juniper::RootNode
             }
-        })
+            // A second impl keeps rustc from inferring the params from the
+            // impl above (rather than from the user's resolver)
+            impl<'a> #params_trait_ident<'a> for __MissingResolver {
+                fn params(_: &'a #parent_rust_type, #(_: #impl_method_param_types),*) -> Self {
+                    __MissingResolver
+                }
+            }
+        };
+        let resolve_call = respan(
+            quote::quote! {
+                #resolver_trait_ident::resolve(
+                    __ResolverFn::call(
+                        <#parent_rust_type>::#method_name_ident,
+                        #params_trait_ident::params(&self.impl_, #(#impl_method_args),*),
+                    )#await_suffix
+                )
+            },
+            parent_rust_type.span(),
+        );
+        let body = quote::quote! {
+            #[allow(dead_code)]
+            trait __ResolverFallback {
+                fn #method_name_ident<'a>(
+                    &'a self,
+                    #(_: #impl_method_param_types),*
+                ) -> __MissingResolver {
+                    __MissingResolver
+                }
+            }
+            impl __ResolverFallback for #parent_rust_type {}
+
+            #resolve_call
+        };
+
+        ResolverMethod {
+            method_name_ident,
+            graphql_name,
+            receiver_params,
+            arguments,
+            return_type,
+            body,
+            resolver_trait,
+            description: field.description.clone(),
+            deprecation_reason: deprecation_reason(&field.directives),
+            is_sync,
+        }
     }
 
-    fn get_wrapper_type_ident(&self, type_name: &String) -> syn::Ident {
-        syn::Ident::new(
-            format!("__{}Wrapper", type_name).as_str(),
+    /**
+     * Produces an expression that evaluates to the Rust value of a default
+     * value from the schema.
+     *
+     * Rather than teach the codegen how to construct every possible Rust type
+     * (enums, input objects, custom scalars, ...) from a literal, we build the
+     * juniper::InputValue that a client would have sent for it and let the
+     * type's own FromInputValue impl do the conversion. The literal has
+     * already been checked against its GraphQL type by
+     * validate_default_values(), so the conversion can only fail for custom
+     * scalars.
+     *
+     * Known limitations:
+     *
+     *  * juniper builds the introspected default from the Rust value (via
+     *    ToInputValue) rather than from the schema's literal, so it isn't
+     *    always served as written. e.g. a default of `{name: "x"}` for an
+     *    input object whose `limit` field defaults to 5 is served as
+     *    `{name: "x", limit: 5}`.
+     *  * Input objects mapped to a Rust type in the `types` option are
+     *    converted by that type's own FromInputValue impl, so the schema's
+     *    defaults for their fields aren't applied -- only whatever defaults the
+     *    Rust type itself declares (if any).
+     */
+    fn generate_default_value(
+        &self,
+        default_value: &graphql_parser::query::Value<'static, String>,
+        rust_type: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let input_value = graphql_value_to_input_value(default_value);
+        let expect_msg = syn::LitStr::new(
+            format!("Invalid default value `{}` for `{}`", default_value, rust_type).as_str(),
             proc_macro2::Span::call_site(),
-        )
+        );
+        quote::quote! {
+            <#rust_type as juniper::FromInputValue<juniper::DefaultScalarValue>>::from_input_value(
+                &#input_value
+            ).expect(#expect_msg)
+        }
     }
 
-    pub fn to_tokens(self) -> Result<proc_macro2::TokenStream, CodegenError> {
-        let mut tokens = proc_macro2::TokenStream::new();
-
-        tokens.extend(self.generate_object_types()?);
-        tokens.extend(self.generate_root_node_wrapper()?);
+    /**
+     * Only the generated wrapper types (not the user-defined Rust types they
+     * wrap) are known to juniper for GraphQL objects, interfaces and unions.
+     * Since user-defined resolvers return the user's own Rust types, any field
+     * whose type refers to one of these must be converted into its wrapper
+     * type before juniper can resolve it.
+     */
+    fn is_wrapped_graphql_type(&self, graphql_type_name: &String) -> bool {
+        self.schema_info.obj_types.contains_key(graphql_type_name)
+            || self.schema_info.interface_types.contains_key(graphql_type_name)
+            || self.schema_info.union_types.contains_key(graphql_type_name)
+    }
 
-        Ok(tokens)
+    fn graphql_type_needs_wrapper(
+        &self,
+        field_type: &graphql_parser::query::Type<'static, String>,
+    ) -> bool {
+        use graphql_parser::query::Type;
+        match field_type {
+            Type::NamedType(name) => self.is_wrapped_graphql_type(name),
+            Type::ListType(inner_type) => self.graphql_type_needs_wrapper(inner_type),
+            Type::NonNullType(inner_type) => self.graphql_type_needs_wrapper(inner_type),
+        }
     }
-}
 
-/**
- * Parse syn::braced!() content for codegen options.
- *
- * e.g. The stuff between the braces in
- *
- *    juniper_schema::from_file!("schema.graphqls", {
- *        <<<<stuff here>>>>
- *    });
- */
-pub struct CodegenOptions {
-    pub context_type: Option<ContextType>,
-    rust_types: HashMap<String, syn::Ident>,
-}
-impl syn::parse::Parse for CodegenOptions {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut context_type = None::<ContextType>;
-        let mut rust_types = None::<HashMap<String, syn::Ident>>;
+    /**
+     * Like graphql_type_to_rust_type(), but produces the type that the
+     * generated (juniper-facing) resolvers return. This is the same as the
+     * user-facing Rust type except that wrapped types are swapped out for
+     * their generated wrappers.
+     */
+    fn graphql_type_to_wrapper_type(
+        &self,
+        field_type: &graphql_parser::query::Type<'static, String>,
+        nullable: bool,
+    ) -> proc_macro2::TokenStream {
+        use graphql_parser::query::Type;
+        match field_type {
+            Type::NamedType(name) if self.is_wrapped_graphql_type(name) => {
+                let wrapper_ident = self.get_wrapper_type_ident(name);
+                if nullable {
+                    quote::quote! { Option<#wrapper_ident> }
+                } else {
+                    quote::quote! { #wrapper_ident }
+                }
+            },
 
-        // Don't have an opinion on which arrow is used for arrow syntax except
-        // that the same arrow is used consistently. Helps when you can't
-        // remember which arrow is expected...it's whichever one you try first.
-        let mut mapping_arrow_token = None::<MapperToken>;
-        while !input.is_empty() {
-            let opt_key = input.parse::<syn::Ident>()?;
-            match opt_key.to_string().as_str() {
-                "context_type" => {
-                    let _ = input.parse::<syn::Token![:]>()?;
-                    if let Some(_) = context_type {
-                        return Err(syn::parse::Error::new(
-                            opt_key.span(),
-                            "`context_type` specified more than once!",
-                        ));
-                    }
-                    let _ = context_type.insert(
-                        ContextType::Global(input.parse::<syn::Type>()?)
-                    );
-                },
+            Type::NamedType(_) => {
+                self.graphql_type_to_rust_type(
+                    field_type,
+                    &proc_macro2::Span::call_site(),
+                    nullable,
+                )
+            },
 
-                "types" => {
-                    if let Some(_) = rust_types {
-                        return Err(syn::parse::Error::new(
-                            opt_key.span(),
-                            "Duplicate `types` specification!",
-                        ));
-                    }
-                    let mut rust_types_map = HashMap::new();
+            Type::ListType(inner_type) => {
+                let inner_type_tokens = self.graphql_type_to_wrapper_type(
+                    inner_type,
+                    /* nullable = */ true,
+                );
+                if nullable {
+                    quote::quote! { Option<Vec<#inner_type_tokens>> }
+                } else {
+                    quote::quote! { Vec<#inner_type_tokens> }
+                }
+            },
 
-                    let _ = input.parse::<syn::Token![:]>()?;
+            Type::NonNullType(inner_type) => {
+                self.graphql_type_to_wrapper_type(
+                    inner_type,
+                    /* nullable = */ false,
+                )
+            },
+        }
+    }
 
-                    let rust_types_tokens;
-                    syn::braced!(rust_types_tokens in input);
+    /**
+     * Given an expression that evaluates to the user-facing Rust type for a
+     * field, produce an expression that converts it into the type returned by
+     * graphql_type_to_wrapper_type() -- mapping through any Option/Vec layers
+     * along the way.
+     */
+    fn wrap_resolved_value(
+        &self,
+        field_type: &graphql_parser::query::Type<'static, String>,
+        nullable: bool,
+        value: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        if !self.graphql_type_needs_wrapper(field_type) {
+            return value;
+        }
 
-                    while !rust_types_tokens.is_empty() {
-                        let graphql_type_ident = rust_types_tokens.parse::<syn::Ident>()?;
-                        match mapping_arrow_token {
-                            Some(MapperToken::SkinnyArrow) => {
-                                rust_types_tokens.parse::<syn::Token![->]>()?;
-                            },
-                            Some(MapperToken::FatArrow) => {
-                                rust_types_tokens.parse::<syn::Token![=>]>()?;
-                            },
-                            None => {
-                                if rust_types_tokens.peek(syn::Token![->]) {
-                                    let _ = mapping_arrow_token.insert(MapperToken::SkinnyArrow);
-                                    rust_types_tokens.parse::<syn::Token![->]>()?;
-                                } else {
-                                    let _ = mapping_arrow_token.insert(MapperToken::FatArrow);
-                                    rust_types_tokens.parse::<syn::Token![=>]>()?;
-                                }
-                            }
-                        };
-                        let rust_type_ident = rust_types_tokens.parse::<syn::Ident>()?;
-                        let _ = rust_types_map.insert(
-                            graphql_type_ident.to_string(),
-                            rust_type_ident,
-                        );
+        use graphql_parser::query::Type;
+        match field_type {
+            Type::NamedType(name) => {
+                let wrapper_ident = self.get_wrapper_type_ident(name);
+                if nullable {
+                    quote::quote! { #value.map(#wrapper_ident::new) }
+                } else {
+                    quote::quote! { #wrapper_ident::new(#value) }
+                }
+            },
 
-                        if rust_types_tokens.peek(syn::Token![,]) {
-                            rust_types_tokens.parse::<syn::Token![,]>()?;
-                        }
+            Type::ListType(inner_type) => {
+                let wrapped_item = self.wrap_resolved_value(
+                    inner_type,
+                    /* nullable = */ true,
+                    quote::quote! { item },
+                );
+                if nullable {
+                    quote::quote! {
+                        #value.map(|items| {
+                            items.into_iter().map(|item| #wrapped_item).collect::<Vec<_>>()
+                        })
+                    }
+                } else {
+                    quote::quote! {
+                        #value.into_iter().map(|item| #wrapped_item).collect::<Vec<_>>()
+                    }
+                }
+            },
+
+            Type::NonNullType(inner_type) => {
+                self.wrap_resolved_value(
+                    inner_type,
+                    /* nullable = */ false,
+                    value,
+                )
+            },
+        }
+    }
+
+    /**
+     * Given an expression that evaluates to the wrapper type for `from_type`
+     * (see graphql_type_to_wrapper_type()), produce an expression that
+     * converts it into the wrapper type for `to_type` -- where `from_type` is
+     * a field type that's valid in place of `to_type` (i.e. an object field
+     * implementing an interface field).
+     *
+     * Objects convert into the wrappers of the interfaces they implement and
+     * the unions they're members of, and interfaces convert into the wrappers
+     * of the interfaces they implement.
+     */
+    fn convert_resolved_value(
+        &self,
+        from_type: &graphql_parser::query::Type<'static, String>,
+        to_type: &graphql_parser::query::Type<'static, String>,
+        from_nullable: bool,
+        to_nullable: bool,
+        value: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        use graphql_parser::query::Type;
+        match (from_type, to_type) {
+            (Type::NonNullType(from_inner), _) => {
+                return self.convert_resolved_value(from_inner, to_type, false, to_nullable, value);
+            },
+            (_, Type::NonNullType(to_inner)) => {
+                return self.convert_resolved_value(from_type, to_inner, from_nullable, false, value);
+            },
+            _ => (),
+        }
+
+        let convert = |value: proc_macro2::TokenStream| match (from_type, to_type) {
+            _ if from_type == to_type => value,
+
+            (Type::ListType(from_item), Type::ListType(to_item)) => {
+                let converted_item = self.convert_resolved_value(
+                    from_item,
+                    to_item,
+                    /* from_nullable = */ true,
+                    /* to_nullable = */ true,
+                    quote::quote! { item },
+                );
+                quote::quote! {
+                    #value.into_iter().map(|item| #converted_item).collect::<Vec<_>>()
+                }
+            },
+
+            (Type::NamedType(from_name), Type::NamedType(to_name)) => {
+                self.convert_wrapped_value(from_name, to_name, value)
+            },
+
+            // Anything else isn't a valid narrowing of the interface field's
+            // type, which rustc will report as a type mismatch
+            _ => value,
+        };
+
+        if from_nullable && from_type != to_type {
+            let converted = convert(quote::quote! { value });
+            quote::quote! { #value.map(|value| #converted) }
+        } else if from_nullable {
+            value
+        } else if to_nullable {
+            let converted = convert(value);
+            quote::quote! { Some(#converted) }
+        } else {
+            convert(value)
+        }
+    }
+
+    fn convert_wrapped_value(
+        &self,
+        from_name: &String,
+        to_name: &String,
+        value: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let from_wrapper_ident = self.get_wrapper_type_ident(from_name);
+        let to_wrapper_ident = self.get_wrapper_type_ident(to_name);
+        if self.schema_info.union_types.contains_key(to_name) {
+            let variant_ident = syn::Ident::new(from_name, proc_macro2::Span::call_site());
+            quote::quote! { #to_wrapper_ident::#variant_ident(#value) }
+        } else if self.schema_info.interface_types.contains_key(from_name) {
+            // juniper names the variants of an interface's enum after the
+            // implementers' (wrapper) types
+            let mut implementer_names = self.schema_info.obj_types.iter().filter(
+                |(_, obj_type)| obj_type.implements_interfaces.contains(from_name)
+            ).map(|(obj_name, _)| obj_name).collect::<Vec<_>>();
+            implementer_names.sort();
+            let implementer_wrapper_idents = implementer_names.iter().map(
+                |obj_name| self.get_wrapper_type_ident(obj_name)
+            );
+            quote::quote! {
+                match #value {
+                    #(
+                        #from_wrapper_ident::#implementer_wrapper_idents(value) => {
+                            #to_wrapper_ident::from(value)
+                        }
+                    ),*
+                }
+            }
+        } else {
+            quote::quote! { #to_wrapper_ident::from(#value) }
+        }
+    }
+
+    /**
+     * Each GraphQL interface gets a juniper interface trait (which only exists
+     * to describe the interface's fields to juniper) along with the juniper
+     * generated enum that holds a value of any of its implementers. We name
+     * that enum like any other wrapper type (`__NodeWrapper`) so that fields
+     * returning an interface can be treated like any other wrapped field.
+     *
+     * The user maps each interface to a Rust enum in the `types: {}` block.
+     * That enum is expected to have one single-field tuple variant per
+     * implementer, named after the implementing GraphQL object type:
+     *
+     *   enum Node {
+     *     User(User),
+     *     Post(Post),
+     *   }
+     *
+     * This is what tells us which concrete GraphQL type a value is.
+     */
+    fn generate_interface_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let context_param = self.options.context_type.as_ref().map(|ctx_type| {
+            let type_ident = ctx_type.juniper_context_type();
+            quote::quote! {
+                context=#type_ident
+            }
+        });
+
+        let interface_defs = self.schema_info.interface_types.iter().map(
+            |(graphql_interface_name, graphql_interface_type)| {
+                let wrapper_ident = self.get_wrapper_type_ident(graphql_interface_name);
+                let trait_ident = self.get_interface_trait_ident(graphql_interface_name);
+                let rust_type = self.options.graphql_type_name_to_rust_type(
+                    graphql_interface_name
+                );
+                let graphql_interface_name_litstr = syn::LitStr::new(
+                    graphql_interface_name.as_str(),
+                    proc_macro2::Span::call_site(),
+                );
+
+                let mut implementer_names = self.schema_info.obj_types.iter().filter(
+                    |(_, obj_type)| obj_type.implements_interfaces.contains(graphql_interface_name)
+                ).map(|(obj_name, _)| obj_name).collect::<Vec<_>>();
+                implementer_names.sort();
+
+                let implementer_wrapper_idents = implementer_names.iter().map(
+                    |obj_name| self.get_wrapper_type_ident(obj_name)
+                ).collect::<Vec<_>>();
+                let implementer_variant_idents = implementer_names.iter().map(
+                    |obj_name| syn::Ident::new(obj_name, proc_macro2::Span::call_site())
+                ).collect::<Vec<_>>();
+
+                let trait_methods = graphql_interface_type.fields.iter().map(|field| {
+                    let resolver_method = self.generate_resolver_method(
+                        field,
+                        graphql_interface_name,
+                        /* is_subscription = */ false,
+                    );
+                    let ResolverMethod {
+                        method_name_ident,
+                        return_type,
+                        ..
+                    } = &resolver_method;
+                    let trait_method_params = resolver_method.graphql_interface_params();
+                    let graphql_attr = resolver_method.graphql_interface_attr();
+                    let doc_attr = doc_attr(&resolver_method.description);
+
+                    quote::quote! {
+                        #doc_attr
+                        #graphql_attr
+                        async fn #method_name_ident(#(#trait_method_params),*) -> #return_type;
+                    }
+                });
+
+                let mut juniper_graphql_attr_params = vec![
+                    quote::quote! { name=#graphql_interface_name_litstr },
+                    quote::quote! { enum=#wrapper_ident },
+                    quote::quote! { for=[#(#implementer_wrapper_idents),*] },
+                ];
+                if let Some(ctx_param) = &context_param {
+                    juniper_graphql_attr_params.push(ctx_param.clone());
+                }
+                if let Some(description) = description_litstr(&graphql_interface_type.description) {
+                    juniper_graphql_attr_params.push(quote::quote! { description=#description });
+                }
+                let doc_attr = doc_attr(&graphql_interface_type.description);
+
+                quote::quote! {
+                    #doc_attr
+                    #[juniper::graphql_interface(#(#juniper_graphql_attr_params),*)]
+                    trait #trait_ident {
+                        #(#trait_methods)*
+                    }
+
+                    impl #wrapper_ident {
+                        pub fn new(impl_: #rust_type) -> Self {
+                            // The mapped type may be any type path (including
+                            // generics), so name it via an alias to match on it
+                            type __Impl = #rust_type;
+                            match impl_ {
+                                #(
+                                    __Impl::#implementer_variant_idents(impl_) => {
+                                        #wrapper_ident::from(#implementer_wrapper_idents::new(impl_))
+                                    }
+                                ),*
+                            }
+                        }
+                    }
+                }
+            }
+        );
+
+        Ok(quote::quote! {
+            #(#interface_defs)*
+        })
+    }
+
+    fn generate_root_node_wrapper(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        // Identify the Query type
+        // TODO: Eventually it should be acceptable for a schema to specify only one of a Query,
+        //       Mutation, or Subscription type.
+        let query_type_name =
+            if let Some(query_type_name) = &self.schema_info.schema_def.query {
+                query_type_name
+            } else {
+                return Err(CodegenError::NoQueryDefinitionFound);
+            };
+
+        let query_rust_type = self.options.graphql_type_name_to_rust_type(query_type_name);
+        let query_wrapper_ident = self.get_wrapper_type_ident(query_type_name);
+
+        // Without a `context_type`, the generated wrappers don't declare a
+        // context so juniper uses its default unit context for them.
+        let context_type = match &self.options.context_type {
+            Some(ctx_type) => {
+                let context_type = ctx_type.juniper_context_type();
+                quote::quote! { #context_type }
+            },
+            None => quote::quote! { () },
+        };
+
+        // The Mutation type is optional. When the schema doesn't declare one,
+        // juniper still needs a mutation root so we fall back to EmptyMutation.
+        let mut new_params = vec![
+            quote::quote! { query: #query_rust_type },
+        ];
+        let (mutation_root_type, mutation_root_value) =
+            match &self.schema_info.schema_def.mutation {
+                Some(mutation_type_name) => {
+                    let mutation_rust_type =
+                        self.options.graphql_type_name_to_rust_type(mutation_type_name);
+                    let mutation_wrapper_ident = self.get_wrapper_type_ident(mutation_type_name);
+                    new_params.push(quote::quote! { mutation: #mutation_rust_type });
+                    (
+                        quote::quote! { #mutation_wrapper_ident },
+                        quote::quote! { #mutation_wrapper_ident::new(mutation) },
+                    )
+                },
+                None => (
+                    quote::quote! { juniper::EmptyMutation<#context_type> },
+                    quote::quote! { juniper::EmptyMutation::new() },
+                ),
+            };
+
+        // Likewise for the Subscription type
+        let (subscription_root_type, subscription_root_value) =
+            match &self.schema_info.schema_def.subscription {
+                Some(subscription_type_name) => {
+                    let subscription_rust_type =
+                        self.options.graphql_type_name_to_rust_type(subscription_type_name);
+                    let subscription_wrapper_ident =
+                        self.get_wrapper_type_ident(subscription_type_name);
+                    new_params.push(quote::quote! { subscription: #subscription_rust_type });
+                    (
+                        quote::quote! { #subscription_wrapper_ident },
+                        quote::quote! { #subscription_wrapper_ident::new(subscription) },
+                    )
+                },
+                None => (
+                    quote::quote! { juniper::EmptySubscription<#context_type> },
+                    quote::quote! { juniper::EmptySubscription::new() },
+                ),
+            };
+
+        let root_node_ident = &self.root_node_ident;
+        Ok(quote::quote! {
+            pub struct #root_node_ident;
+            impl #root_node_ident {
+                pub fn new(#(#new_params),*) -> juniper::RootNode<
+                    'static,
+                    #query_wrapper_ident,
+                    #mutation_root_type,
+                    #subscription_root_type,
+                > {
+                    juniper::RootNode::new(
+                        #query_wrapper_ident::new(query),
+                        #mutation_root_value,
+                        #subscription_root_value,
+                    )
+                }
+
+                // TODO: Impl other delegators to other relevant methods on
+                //       juniper::RootNode
+            }
+        })
+    }
+
+    /**
+     * Each GraphQL union gets a generated enum (`__SearchResultWrapper`) with
+     * one variant per member type, each holding that member's wrapper type.
+     *
+     * Like interfaces, the user maps a union to their own Rust enum in the
+     * `types: {}` block. That enum must have exactly one single-field tuple
+     * variant per union member, named after the member's GraphQL type:
+     *
+     *   enum SearchResult {
+     *     User(User),
+     *     Post(Post),
+     *   }
+     *
+     * The generated conversion from the user's enum into the wrapper enum
+     * matches on every member's variant (and nothing else), so rustc reports
+     * a compile error if the enum is missing a member's variant or has
+     * variants that aren't members of the union.
+     */
+    fn generate_union_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let context_param = self.options.context_type.as_ref().map(|ctx_type| {
+            let type_ident = ctx_type.juniper_context_type();
+            quote::quote! {
+                context=#type_ident
+            }
+        });
+
+        let union_defs = self.schema_info.union_types.iter().map(
+            |(graphql_union_name, graphql_union_type)| {
+                let wrapper_ident = self.get_wrapper_type_ident(graphql_union_name);
+                let rust_type = self.options.graphql_type_name_to_rust_type(
+                    graphql_union_name
+                );
+                let graphql_union_name_litstr = syn::LitStr::new(
+                    graphql_union_name.as_str(),
+                    proc_macro2::Span::call_site(),
+                );
+
+                let member_wrapper_idents = graphql_union_type.types.iter().map(
+                    |member_name| self.get_wrapper_type_ident(member_name)
+                ).collect::<Vec<_>>();
+                let member_variant_idents = graphql_union_type.types.iter().map(
+                    |member_name| syn::Ident::new(member_name, proc_macro2::Span::call_site())
+                ).collect::<Vec<_>>();
+
+                let mut juniper_graphql_attr_params = vec![
+                    quote::quote! { name=#graphql_union_name_litstr },
+                ];
+                if let Some(ctx_param) = &context_param {
+                    juniper_graphql_attr_params.push(ctx_param.clone());
+                }
+                if let Some(description) = description_litstr(&graphql_union_type.description) {
+                    juniper_graphql_attr_params.push(quote::quote! { description=#description });
+                }
+                let doc_attr = doc_attr(&graphql_union_type.description);
+
+                quote::quote! {
+                    #doc_attr
+                    #[derive(juniper::GraphQLUnion)]
+                    #[graphql(#(#juniper_graphql_attr_params),*)]
+                    enum #wrapper_ident {
+                        #(#member_variant_idents(#member_wrapper_idents)),*
+                    }
+
+                    impl #wrapper_ident {
+                        pub fn new(impl_: #rust_type) -> Self {
+                            type __Impl = #rust_type;
+                            match impl_ {
+                                #(
+                                    __Impl::#member_variant_idents(impl_) => {
+                                        #wrapper_ident::#member_variant_idents(
+                                            #member_wrapper_idents::new(impl_)
+                                        )
+                                    }
+                                ),*
+                            }
+                        }
+                    }
+                }
+            }
+        );
+
+        Ok(quote::quote! {
+            #(#union_defs)*
+        })
+    }
+
+    /**
+     * GraphQL enums map directly onto a user-defined Rust enum (no wrapper
+     * type). Rather than asking the user to annotate their enum with
+     * `#[derive(juniper::GraphQLEnum)]` and a `#[graphql(name = "...")]` for
+     * every variant, we implement juniper's enum traits for it here using a
+     * naming convention: The GraphQL value `NEW_HOPE` maps to the Rust variant
+     * `NewHope`.
+     *
+     * The generated impls match exhaustively over the Rust enum in both
+     * directions, so rustc will issue a compile error if a GraphQL enum value
+     * has no corresponding Rust variant or if the Rust enum has a variant that
+     * doesn't correspond to any GraphQL enum value.
+     *
+     * (Because these are impls of juniper traits on the user's type, the
+     * mapped enum must be defined in the same crate as the macro invocation.)
+     */
+    fn generate_enum_types(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let enum_defs = self.schema_info.enum_types.iter().map(
+            |(graphql_enum_name, graphql_enum_type)| {
+                let rust_type = self.options.graphql_type_name_to_rust_type(
+                    graphql_enum_name
+                );
+                let rust_type_span = rust_type.span();
+                let graphql_enum_name_litstr = syn::LitStr::new(
+                    graphql_enum_name.as_str(),
+                    proc_macro2::Span::call_site(),
+                );
+
+                let value_name_litstrs = graphql_enum_type.values.iter().map(
+                    |value| syn::LitStr::new(value.name.as_str(), proc_macro2::Span::call_site())
+                ).collect::<Vec<_>>();
+                let value_descriptions = graphql_enum_type.values.iter().map(
+                    |value| match description_litstr(&value.description) {
+                        Some(description) => quote::quote! { Some(String::from(#description)) },
+                        None => quote::quote! { None },
+                    }
+                ).collect::<Vec<_>>();
+                let value_deprecation_statuses = graphql_enum_type.values.iter().map(
+                    |value| match deprecation_reason(&value.directives) {
+                        Some(reason) => quote::quote! {
+                            juniper::meta::DeprecationStatus::Deprecated(Some(String::from(#reason)))
+                        },
+                        None => quote::quote! { juniper::meta::DeprecationStatus::Current },
+                    }
+                ).collect::<Vec<_>>();
+                let description_call = description_litstr(&graphql_enum_type.description).map(
+                    |description| quote::quote! { .description(#description) }
+                );
+                let variant_idents = graphql_enum_type.values.iter().map(
+                    |value| syn::Ident::new(
+                        screaming_snake_case_to_camel_case(&value.name).as_str(),
+                        rust_type_span,
+                    )
+                ).collect::<Vec<_>>();
+
+                quote::quote! {
+                    impl<__S> juniper::marker::IsInputType<__S> for #rust_type
+                        where __S: juniper::ScalarValue {}
+
+                    impl<__S> juniper::marker::IsOutputType<__S> for #rust_type
+                        where __S: juniper::ScalarValue {}
+
+                    impl<__S> juniper::GraphQLType<__S> for #rust_type
+                        where __S: juniper::ScalarValue
+                    {
+                        fn name(_: &()) -> Option<&'static str> {
+                            Some(#graphql_enum_name_litstr)
+                        }
+
+                        fn meta<'r>(
+                            _: &(),
+                            registry: &mut juniper::Registry<'r, __S>,
+                        ) -> juniper::meta::MetaType<'r, __S>
+                            where __S: 'r
+                        {
+                            registry.build_enum_type::<#rust_type>(&(), &[
+                                #(juniper::meta::EnumValue {
+                                    name: #value_name_litstrs.to_string(),
+                                    description: #value_descriptions,
+                                    deprecation_status: #value_deprecation_statuses,
+                                },)*
+                            ])#description_call.into_meta()
+                        }
+                    }
+
+                    impl<__S> juniper::GraphQLValue<__S> for #rust_type
+                        where __S: juniper::ScalarValue
+                    {
+                        type Context = ();
+                        type TypeInfo = ();
+
+                        fn type_name<'__i>(&self, info: &'__i Self::TypeInfo) -> Option<&'__i str> {
+                            <Self as juniper::GraphQLType<__S>>::name(info)
+                        }
+
+                        fn resolve(
+                            &self,
+                            _: &(),
+                            _: Option<&[juniper::Selection<__S>]>,
+                            _: &juniper::Executor<Self::Context, __S>,
+                        ) -> juniper::ExecutionResult<__S> {
+                            Ok(match self {
+                                #(Self::#variant_idents => {
+                                    juniper::Value::scalar(String::from(#value_name_litstrs))
+                                },)*
+                            })
+                        }
+                    }
+
+                    impl<__S> juniper::GraphQLValueAsync<__S> for #rust_type
+                        where __S: juniper::ScalarValue + Send + Sync,
+                              Self: Sync
+                    {
+                        fn resolve_async<'a>(
+                            &'a self,
+                            info: &'a Self::TypeInfo,
+                            selection_set: Option<&'a [juniper::Selection<__S>]>,
+                            executor: &'a juniper::Executor<Self::Context, __S>,
+                        ) -> juniper::BoxFuture<'a, juniper::ExecutionResult<__S>> {
+                            let v = juniper::GraphQLValue::resolve(self, info, selection_set, executor);
+                            Box::pin(juniper::futures::future::ready(v))
+                        }
+                    }
+
+                    impl<__S> juniper::FromInputValue<__S> for #rust_type
+                        where __S: juniper::ScalarValue
+                    {
+                        fn from_input_value(v: &juniper::InputValue<__S>) -> Option<#rust_type> {
+                            match v.as_enum_value().or_else(|| v.as_string_value()) {
+                                #(Some(#value_name_litstrs) => Some(Self::#variant_idents),)*
+                                _ => None,
+                            }
+                        }
+                    }
+
+                    impl<__S> juniper::ToInputValue<__S> for #rust_type
+                        where __S: juniper::ScalarValue
+                    {
+                        fn to_input_value(&self) -> juniper::InputValue<__S> {
+                            match self {
+                                #(Self::#variant_idents => {
+                                    juniper::InputValue::enum_value(#value_name_litstrs)
+                                },)*
+                            }
+                        }
+                    }
+                }
+            }
+        );
+
+        Ok(quote::quote! {
+            #(#enum_defs)*
+        })
+    }
+
+    /**
+     * What a wrapper resolver gets back when the user-defined type has no
+     * resolver for the field (see generate_resolver_method()).
+     *
+     * It can be `.await`ed like the result of an async resolver, but it's
+     * never a valid result for any field.
+     */
+    fn generate_missing_resolver_type(&self) -> proc_macro2::TokenStream {
+        let resolver_fn = self.generate_resolver_fn_trait();
+        quote::quote! {
+            #resolver_fn
+
+            #[doc(hidden)]
+            struct __MissingResolver;
+            impl std::future::IntoFuture for __MissingResolver {
+                type Output = Self;
+                type IntoFuture = std::future::Ready<Self>;
+                fn into_future(self) -> Self::IntoFuture {
+                    std::future::ready(self)
+                }
+            }
+        }
+    }
+
+    /**
+     * Calls a user-defined resolver with its params as a tuple, so that the
+     * tuple's type can be checked against the params that the schema requires
+     * (see generate_resolver_method()).
+     *
+     * There's an impl for each number of params up to the most that any
+     * resolver takes (counting `&self` and the context), but at least 16 so
+     * that a resolver with a few too many params is still reported with the
+     * signature its field requires.
+     */
+    fn generate_resolver_fn_trait(&self) -> proc_macro2::TokenStream {
+        let max_field_args = self.schema_info.obj_types.values().flat_map(
+            |obj_type| obj_type.fields.iter().map(|field| field.arguments.len())
+        ).max().unwrap_or(0);
+        let max_params = (1 + self.options.context_type.iter().count() + max_field_args).max(16);
+
+        let impls = (1..=max_params).map(|num_params| {
+            let param_type_idents = (0..num_params).map(
+                |idx| syn::Ident::new(format!("__A{}", idx).as_str(), proc_macro2::Span::call_site())
+            ).collect::<Vec<_>>();
+            let param_indices = (0..num_params).map(syn::Index::from);
+            quote::quote! {
+                impl<__F, __R, #(#param_type_idents),*> __ResolverFn<(#(#param_type_idents,)*)> for __F
+                    where __F: FnOnce(#(#param_type_idents),*) -> __R
+                {
+                    type Output = __R;
+                    fn call(self, params: (#(#param_type_idents,)*)) -> __R {
+                        self(#(params.#param_indices),*)
+                    }
+                }
+            }
+        });
+
+        quote::quote! {
+            #[doc(hidden)]
+            #[diagnostic::on_unimplemented(
+                message = "`{Self}` takes more params than its GraphQL field's resolver can",
+            )]
+            trait __ResolverFn<Params> {
+                type Output;
+                fn call(self, params: Params) -> Self::Output;
+            }
+            #(#impls)*
+        }
+    }
+
+    /**
+     * Describes the user-defined resolver that a field requires, as it would
+     * be written on the user-defined type (for error messages).
+     */
+    fn describe_resolver_signature(
+        &self,
+        method_name_ident: &syn::Ident,
+        parent_type_name: &String,
+        arguments: &[ResolverArgument],
+        rust_type: &proc_macro2::TokenStream,
+        is_sync: bool,
+        is_subscription: bool,
+    ) -> String {
+        let mut params = vec![String::from("&self")];
+        if let Some(ctx_type) = &self.options.context_type {
+            let ctx_type = ctx_type.type_context_type(parent_type_name).unwrap_or(
+                ctx_type.juniper_context_type()
+            );
+            params.push(format!("ctx: &{}", tokens_to_string(ctx_type.to_token_stream())));
+        }
+        params.extend(arguments.iter().map(|ResolverArgument { ident, rust_type, .. }| {
+            format!("{}: {}", ident, tokens_to_string(rust_type.clone()))
+        }));
+
+        let return_type = if is_subscription {
+            format!("impl Stream<Item = {}>", tokens_to_string(rust_type.clone()))
+        } else {
+            tokens_to_string(rust_type.clone())
+        };
+        format!(
+            "{}fn {}({}) -> {}",
+            if is_sync { "" } else { "async " },
+            method_name_ident,
+            params.join(", "),
+            return_type,
+        )
+    }
+
+    fn get_interface_trait_ident(&self, type_name: &String) -> syn::Ident {
+        syn::Ident::new(
+            format!("__{}Interface", type_name).as_str(),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    /**
+     * `__Resolver_5Order_9itemCount`
+     *
+     * Both parts are length-prefixed so that no two (type, field) pairs share
+     * a trait name (e.g. `Order.itemCount` and `OrderItem.count`).
+     */
+    fn get_resolver_trait_ident(&self, type_name: &String, field_name: &String) -> syn::Ident {
+        syn::Ident::new(
+            format!(
+                "__Resolver_{}{}_{}{}",
+                type_name.len(),
+                type_name,
+                field_name.len(),
+                field_name,
+            ).as_str(),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    /**
+     * `__ResolverParams_5Order_9itemCount`
+     */
+    fn get_resolver_params_trait_ident(&self, type_name: &String, field_name: &String) -> syn::Ident {
+        syn::Ident::new(
+            format!(
+                "__ResolverParams_{}{}_{}{}",
+                type_name.len(),
+                type_name,
+                field_name.len(),
+                field_name,
+            ).as_str(),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    fn get_wrapper_type_ident(&self, type_name: &String) -> syn::Ident {
+        syn::Ident::new(
+            format!("__{}Wrapper", type_name).as_str(),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    pub fn to_tokens(self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        self.generate_tokens().map_err(|e| e.in_schema_file(&self.schema_file))
+    }
+
+    fn generate_tokens(&self) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let mut tokens = proc_macro2::TokenStream::new();
+
+        tokens.extend(self.generate_missing_resolver_type());
+        tokens.extend(self.generate_enum_types()?);
+        tokens.extend(self.generate_input_object_types()?);
+        tokens.extend(self.generate_interface_types()?);
+        tokens.extend(self.generate_union_types()?);
+        tokens.extend(self.generate_object_types()?);
+        tokens.extend(self.generate_root_node_wrapper()?);
+
+        Ok(tokens)
+    }
+}
+
+struct ResolverMethod {
+    method_name_ident: syn::Ident,
+    graphql_name: syn::LitStr,
+    receiver_params: Vec<proc_macro2::TokenStream>,
+    arguments: Vec<ResolverArgument>,
+    return_type: proc_macro2::TokenStream,
+    body: proc_macro2::TokenStream,
+    resolver_trait: proc_macro2::TokenStream,
+    description: Option<String>,
+    deprecation_reason: Option<String>,
+    is_sync: bool,
+}
+impl ResolverMethod {
+    fn wrapper_method_params(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut params = self.receiver_params.clone();
+        params.extend(self.arguments.iter().map(
+            |ResolverArgument { ident, rust_type, .. }| quote::quote! {
+                #ident: #rust_type
+            }
+        ));
+        params
+    }
+
+    /**
+     * #[graphql_object] methods describe themselves and their arguments to
+     * juniper with a single method-level `#[graphql(...)]` attribute. We
+     * always specify the GraphQL name of each argument so that juniper doesn't
+     * have to guess it from the (snake_cased) Rust param name.
+     *
+     * Descriptions are passed explicitly (rather than left for juniper to pick
+     * up from the doc comments) because juniper trims whitespace from each
+     * line of a doc comment.
+     */
+    fn graphql_object_attr(&self) -> Option<proc_macro2::TokenStream> {
+        let mut attr_params = self.graphql_field_params();
+
+        if !self.arguments.is_empty() {
+            let argument_attrs = self.arguments.iter().map(
+                |ResolverArgument { ident, graphql_name, default, description, .. }| {
+                    let default_param = default.as_ref().map(|default| quote::quote! {
+                        , default=#default
+                    });
+                    let description_param = description_litstr(description).map(
+                        |description| quote::quote! { , description=#description }
+                    );
+                    quote::quote! {
+                        #ident(name=#graphql_name #default_param #description_param)
+                    }
+                }
+            );
+            attr_params.push(quote::quote! { arguments(#(#argument_attrs),*) });
+        }
+
+        if attr_params.is_empty() {
+            return None;
+        }
+        Some(quote::quote! {
+            #[graphql(#(#attr_params),*)]
+        })
+    }
+
+    fn graphql_interface_attr(&self) -> Option<proc_macro2::TokenStream> {
+        let attr_params = self.graphql_field_params();
+        if attr_params.is_empty() {
+            return None;
+        }
+        Some(quote::quote! {
+            #[graphql(#(#attr_params),*)]
+        })
+    }
+
+    /**
+     * The field-level params understood by both #[graphql_object] methods and
+     * #[graphql_interface] trait methods.
+     */
+    fn graphql_field_params(&self) -> Vec<proc_macro2::TokenStream> {
+        let graphql_name = &self.graphql_name;
+        let mut params = vec![
+            quote::quote! { name=#graphql_name },
+        ];
+        if let Some(description) = description_litstr(&self.description) {
+            params.push(quote::quote! { description=#description });
+        }
+        if let Some(reason) = &self.deprecation_reason {
+            let reason = syn::LitStr::new(reason.as_str(), proc_macro2::Span::call_site());
+            params.push(quote::quote! { deprecated=#reason });
+        }
+        params
+    }
+
+    /**
+     * #[graphql_interface] trait methods, on the other hand, describe their
+     * arguments with attributes on each param.
+     */
+    fn graphql_interface_params(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut params = self.receiver_params.clone();
+        params.extend(self.arguments.iter().map(
+            |ResolverArgument { ident, graphql_name, rust_type, default, description }| {
+                let default_param = default.as_ref().map(|default| quote::quote! {
+                    , default=#default
+                });
+                let description_param = description_litstr(description).map(
+                    |description| quote::quote! { , description=#description }
+                );
+                quote::quote! {
+                    #[graphql(name=#graphql_name #default_param #description_param)]
+                    #ident: #rust_type
+                }
+            }
+        ));
+        params
+    }
+}
+
+struct ResolverArgument {
+    ident: syn::Ident,
+    graphql_name: syn::LitStr,
+    rust_type: proc_macro2::TokenStream,
+    default: Option<proc_macro2::TokenStream>,
+    description: Option<String>,
+}
+
+/**
+ * Points all of the given tokens at `span` (without changing how any of their
+ * identifiers resolve) so that errors in them are reported there.
+ */
+fn respan(tokens: proc_macro2::TokenStream, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    tokens.into_iter().map(|mut token| {
+        if let proc_macro2::TokenTree::Group(group) = &token {
+            let mut respanned_group = proc_macro2::Group::new(
+                group.delimiter(),
+                respan(group.stream(), span),
+            );
+            respanned_group.set_span(group.span().located_at(span));
+            return proc_macro2::TokenTree::Group(respanned_group);
+        }
+        token.set_span(token.span().located_at(span));
+        token
+    }).collect()
+}
+
+/**
+ * proc_macro2 puts spaces between all tokens when stringifying them, which is
+ * hard to read in error messages (`Option < juniper :: ID >`).
+ */
+fn tokens_to_string(tokens: proc_macro2::TokenStream) -> String {
+    tokens.to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+        .replace("' ", "'")
+}
+
+fn description_litstr(description: &Option<String>) -> Option<syn::LitStr> {
+    description.as_ref().map(
+        |description| syn::LitStr::new(description.as_str(), proc_macro2::Span::call_site())
+    )
+}
+
+/**
+ * Returns the reason given by a `@deprecated` directive (if there is one).
+ *
+ * Per the GraphQL spec, `@deprecated` without a `reason` is the same as
+ * `@deprecated(reason: "No longer supported")`.
+ *
+ * Note that juniper (as of 0.15) has no notion of deprecated arguments or
+ * input fields, so `@deprecated` is only honored on fields and enum values.
+ */
+fn deprecation_reason(
+    directives: &[graphql_parser::schema::Directive<'static, String>],
+) -> Option<String> {
+    use graphql_parser::query::Value;
+    let directive = directives.iter().find(|directive| directive.name == "deprecated")?;
+    let reason = directive.arguments.iter().find_map(|(arg_name, value)| {
+        match (arg_name.as_str(), value) {
+            ("reason", Value::String(reason)) => Some(reason.clone()),
+            _ => None,
+        }
+    });
+    Some(reason.unwrap_or_else(|| String::from("No longer supported")))
+}
+
+/**
+ * Schema descriptions are also emitted as doc comments on the generated items
+ * so that they show up when hovering over them in an editor.
+ */
+fn doc_attr(description: &Option<String>) -> Option<proc_macro2::TokenStream> {
+    description_litstr(description).map(|description| quote::quote! {
+        #[doc = #description]
+    })
+}
+
+/**
+ * A default of `null` is the same as no default at all as far as the Rust type
+ * of an argument (or input object field) is concerned.
+ */
+fn has_non_null_default(input_value: &graphql_parser::schema::InputValue<'static, String>) -> bool {
+    use graphql_parser::query::Value;
+    match &input_value.default_value {
+        None | Some(Value::Null) => false,
+        Some(_) => true,
+    }
+}
+
+/**
+ * Produces an expression that constructs the juniper::InputValue equivalent of
+ * a (constant) GraphQL value from the schema.
+ */
+fn graphql_value_to_input_value(
+    value: &graphql_parser::query::Value<'static, String>,
+) -> proc_macro2::TokenStream {
+    use graphql_parser::query::Value;
+    match value {
+        Value::Int(num) => {
+            let num = num.as_i64().unwrap_or_default() as i32;
+            quote::quote! { juniper::InputValue::<juniper::DefaultScalarValue>::scalar(#num) }
+        },
+        Value::Float(num) => {
+            quote::quote! { juniper::InputValue::<juniper::DefaultScalarValue>::scalar(#num) }
+        },
+        Value::String(string) => {
+            quote::quote! {
+                juniper::InputValue::<juniper::DefaultScalarValue>::scalar(String::from(#string))
+            }
+        },
+        Value::Boolean(boolean) => {
+            quote::quote! { juniper::InputValue::<juniper::DefaultScalarValue>::scalar(#boolean) }
+        },
+        Value::Enum(enum_value) => {
+            quote::quote! { juniper::InputValue::<juniper::DefaultScalarValue>::enum_value(#enum_value) }
+        },
+        Value::List(items) => {
+            let items = items.iter().map(graphql_value_to_input_value);
+            quote::quote! {
+                juniper::InputValue::<juniper::DefaultScalarValue>::list(vec![#(#items),*])
+            }
+        },
+        Value::Object(fields) => {
+            let field_names = fields.keys();
+            let field_values = fields.values().map(graphql_value_to_input_value);
+            quote::quote! {
+                juniper::InputValue::<juniper::DefaultScalarValue>::parsed_object(vec![
+                    #((
+                        juniper::Spanning::unlocated(String::from(#field_names)),
+                        juniper::Spanning::unlocated(#field_values),
+                    )),*
+                ])
+            }
+        },
+        // Variables can't appear in a schema, so treat them like a null
+        Value::Null | Value::Variable(_) => {
+            quote::quote! { juniper::InputValue::<juniper::DefaultScalarValue>::null() }
+        },
+    }
+}
+
+/**
+ * Parse syn::braced!() content for codegen options.
+ *
+ * e.g. The stuff between the braces in
+ *
+ *    juniper_schema::from_file!("schema.graphqls", {
+ *        <<<<stuff here>>>>
+ *    });
+ */
+pub struct CodegenOptions {
+    pub context_type: Option<ContextType>,
+    rust_types: HashMap<String, syn::Type>,
+    rust_types_span: proc_macro2::Span,
+    scalar_types: HashMap<String, syn::Type>,
+    sync_resolvers: Vec<SyncResolver>,
+    rename: RenameStrategy,
+}
+impl syn::parse::Parse for CodegenOptions {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut context_type = None::<ContextType>;
+        let mut rust_types = None::<HashMap<String, syn::Type>>;
+        let mut rust_types_span = proc_macro2::Span::call_site();
+        let mut scalar_types = None::<HashMap<String, syn::Type>>;
+        let mut sync_resolvers = None::<Vec<SyncResolver>>;
+        let mut rename = None::<RenameStrategy>;
+
+        // Don't have an opinion on which arrow is used for arrow syntax except
+        // that the same arrow is used consistently. Helps when you can't
+        // remember which arrow is expected...it's whichever one you try first.
+        let mut mapping_arrow_token = None::<MapperToken>;
+        while !input.is_empty() {
+            let opt_key = input.parse::<syn::Ident>()?;
+            match opt_key.to_string().as_str() {
+                "context_type" => {
+                    let _ = input.parse::<syn::Token![:]>()?;
+                    if let Some(_) = context_type {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "`context_type` specified more than once!",
+                        ));
+                    }
+                    let _ = context_type.insert(
+                        if input.peek(syn::token::Brace) {
+                            parse_per_type_context_type(input)?
+                        } else {
+                            ContextType::Global(input.parse::<syn::Type>()?)
+                        }
+                    );
+                },
+
+                "types" => {
+                    if let Some(_) = rust_types {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "Duplicate `types` specification!",
+                        ));
+                    }
+                    let mut rust_types_map = HashMap::new();
+
+                    let _ = input.parse::<syn::Token![:]>()?;
+
+                    let rust_types_tokens;
+                    let rust_types_brace = syn::braced!(rust_types_tokens in input);
+                    rust_types_span = rust_types_brace.span.join();
+
+                    while !rust_types_tokens.is_empty() {
+                        let graphql_type_ident = rust_types_tokens.parse::<syn::Ident>()?;
+                        MapperToken::parse(&rust_types_tokens, &mut mapping_arrow_token)?;
+                        let rust_type = rust_types_tokens.parse::<syn::Type>()?;
+                        let _ = rust_types_map.insert(
+                            graphql_type_ident.to_string(),
+                            rust_type,
+                        );
+
+                        if rust_types_tokens.peek(syn::Token![,]) {
+                            rust_types_tokens.parse::<syn::Token![,]>()?;
+                        }
                     }
 
                     let _ = rust_types.insert(rust_types_map);
                 },
 
+                "scalars" => {
+                    if let Some(_) = scalar_types {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "Duplicate `scalars` specification!",
+                        ));
+                    }
+                    let mut scalar_types_map = HashMap::new();
+
+                    let _ = input.parse::<syn::Token![:]>()?;
+
+                    let scalar_types_tokens;
+                    syn::braced!(scalar_types_tokens in input);
+
+                    while !scalar_types_tokens.is_empty() {
+                        let graphql_scalar_ident = scalar_types_tokens.parse::<syn::Ident>()?;
+                        MapperToken::parse(&scalar_types_tokens, &mut mapping_arrow_token)?;
+                        let rust_type = scalar_types_tokens.parse::<syn::Type>()?;
+                        let _ = scalar_types_map.insert(
+                            graphql_scalar_ident.to_string(),
+                            rust_type,
+                        );
+
+                        if scalar_types_tokens.peek(syn::Token![,]) {
+                            scalar_types_tokens.parse::<syn::Token![,]>()?;
+                        }
+                    }
+
+                    let _ = scalar_types.insert(scalar_types_map);
+                },
+
+                // `sync_resolvers: [User, Query.version]` lists the object
+                // types (all fields) and individual fields whose user-defined
+                // resolvers are plain (non-async) fns.
+                "sync_resolvers" => {
+                    if let Some(_) = sync_resolvers {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "Duplicate `sync_resolvers` specification!",
+                        ));
+                    }
+                    let mut sync_resolvers_list = vec![];
+
+                    let _ = input.parse::<syn::Token![:]>()?;
+
+                    let sync_resolvers_tokens;
+                    syn::bracketed!(sync_resolvers_tokens in input);
+
+                    while !sync_resolvers_tokens.is_empty() {
+                        let type_ident = sync_resolvers_tokens.parse::<syn::Ident>()?;
+                        let field_ident =
+                            if sync_resolvers_tokens.peek(syn::Token![.]) {
+                                sync_resolvers_tokens.parse::<syn::Token![.]>()?;
+                                Some(sync_resolvers_tokens.parse::<syn::Ident>()?)
+                            } else {
+                                None
+                            };
+                        sync_resolvers_list.push(SyncResolver { type_ident, field_ident });
+
+                        if sync_resolvers_tokens.peek(syn::Token![,]) {
+                            sync_resolvers_tokens.parse::<syn::Token![,]>()?;
+                        }
+                    }
+
+                    let _ = sync_resolvers.insert(sync_resolvers_list);
+                },
+
+                "rename" => {
+                    let _ = input.parse::<syn::Token![:]>()?;
+                    if let Some(_) = rename {
+                        return Err(syn::parse::Error::new(
+                            opt_key.span(),
+                            "`rename` specified more than once!",
+                        ));
+                    }
+                    let _ = rename.insert(input.parse::<RenameStrategy>()?);
+                },
+
                 other => {
                     return Err(syn::parse::Error::new(
                         opt_key.span(),
@@ -502,6 +2185,10 @@ impl syn::parse::Parse for CodegenOptions {
         Ok(CodegenOptions {
             context_type,
             rust_types,
+            rust_types_span,
+            scalar_types: scalar_types.unwrap_or_default(),
+            sync_resolvers: sync_resolvers.unwrap_or_default(),
+            rename: rename.unwrap_or_default(),
         })
     }
 }
@@ -516,49 +2203,716 @@ impl Default for CodegenOptions {
 }
 */
 impl CodegenOptions {
-    pub fn validate(&self, schema_info: &SchemaInfo) -> Result<(), CodegenError> {
-        // TODO: Now that the options.rust_types is mandatory, need to update
-        //       this check to verify that all graphql types have a
-        //       corresponding, mapped Rust type.
+    pub fn validate(
+        &self,
+        schema_info: &SchemaInfo,
+        schema_file: &SchemaFile,
+    ) -> Result<(), CodegenError> {
+        validate_type_references(schema_info)?;
+        validate_default_values(schema_info)?;
+
+        // Every object, interface, union, and enum type in the schema needs a
+        // Rust type to resolve it. Input objects are optional since we generate
+        // a struct for any that aren't mapped.
+        let mut unmapped_types = vec![];
+        unmapped_types.extend(schema_info.obj_types.values().map(
+            |obj_type| ("object", &obj_type.name, obj_type.position)
+        ));
+        unmapped_types.extend(schema_info.interface_types.values().map(
+            |interface_type| ("interface", &interface_type.name, interface_type.position)
+        ));
+        unmapped_types.extend(schema_info.union_types.values().map(
+            |union_type| ("union", &union_type.name, union_type.position)
+        ));
+        unmapped_types.extend(schema_info.enum_types.values().map(
+            |enum_type| ("enum", &enum_type.name, enum_type.position)
+        ));
+        unmapped_types.retain(|(_, name, _)| !self.rust_types.contains_key(*name));
+        if !unmapped_types.is_empty() {
+            unmapped_types.sort_by_key(|(_, _, position)| *position);
+            let type_list = unmapped_types.iter().map(|(kind, name, position)| {
+                format!("`{}` ({}, defined at {})", name, kind, schema_file.location(position))
+            }).collect::<Vec<_>>().join(", ");
+            return Err(CodegenError::UnmappedGraphQLType(format!(
+                "No Rust type specified for GraphQL type(s): {}. Every object, \
+                interface, union, and enum type must be mapped to a Rust type in \
+                the `types` option (e.g. `types: {{ {} -> MyRustType }}`).",
+                type_list,
+                unmapped_types[0].1,
+            ), self.rust_types_span));
+        }
 
         // All entries in rust_types should map to an actual type specified in
         // the schema
-        for (graphql_type_name, rust_type_ident) in self.rust_types.iter() {
+        for (graphql_type_name, rust_type) in self.rust_types.iter() {
             if schema_info.enum_types.contains_key(graphql_type_name) {
                 continue;
             }
 
+            if schema_info.input_obj_types.contains_key(graphql_type_name) {
+                continue;
+            }
+
+            if schema_info.interface_types.contains_key(graphql_type_name) {
+                continue;
+            }
+
             if schema_info.obj_types.contains_key(graphql_type_name) {
                 continue;
             }
 
+            if schema_info.union_types.contains_key(graphql_type_name) {
+                continue;
+            }
+
             return Err(CodegenError::UndefinedGraphQLType(format!(
                 "Error mapping GraphQLType(`{}`) -> RustType(`{}`): `{}` \
                 is not a type defined in your GraphQL schema.",
                 &graphql_type_name,
-                rust_type_ident.to_string(),
+                rust_type.to_token_stream(),
                 graphql_type_name,
             )));
         }
 
+        // Every custom scalar in the schema needs a Rust type to represent it,
+        // and every entry in scalar_types should be a scalar in the schema.
+        let mut unmapped_scalars = schema_info.scalar_types.values().filter(
+            |scalar_type| !self.scalar_types.contains_key(&scalar_type.name)
+        ).collect::<Vec<_>>();
+        if !unmapped_scalars.is_empty() {
+            unmapped_scalars.sort_by_key(|scalar_type| scalar_type.position);
+            let scalar_list = unmapped_scalars.iter().map(|scalar_type| {
+                format!(
+                    "`{}` (defined at {})",
+                    scalar_type.name,
+                    schema_file.location(&scalar_type.position),
+                )
+            }).collect::<Vec<_>>().join(", ");
+            return Err(CodegenError::UnmappedGraphQLScalar(format!(
+                "No Rust type specified for GraphQL scalar(s): {}. Custom scalars \
+                must be mapped to a Rust type in the `scalars` option (e.g. \
+                `scalars: {{ {} -> MyRustType }}`).",
+                scalar_list,
+                unmapped_scalars[0].name,
+            )));
+        }
+
+        for (graphql_scalar_name, rust_type) in self.scalar_types.iter() {
+            if schema_info.scalar_types.contains_key(graphql_scalar_name) {
+                continue;
+            }
+
+            return Err(CodegenError::UndefinedGraphQLType(format!(
+                "Error mapping GraphQLScalar(`{}`) -> RustType(`{}`): `{}` \
+                is not a scalar defined in your GraphQL schema.",
+                &graphql_scalar_name,
+                rust_type.to_token_stream(),
+                graphql_scalar_name,
+            )));
+        }
+
+        // Per-type context entries for an exact type name should name a type
+        // with resolvers (i.e. an object type) in the schema
+        if let Some(ContextType::PerType { type_contexts, .. }) = &self.context_type {
+            for (pattern, context_type) in type_contexts.iter() {
+                let graphql_type_name = pattern.name.to_string();
+                if pattern.is_prefix || schema_info.obj_types.contains_key(&graphql_type_name) {
+                    continue;
+                }
+
+                return Err(CodegenError::UndefinedGraphQLType(format!(
+                    "Error mapping GraphQLType(`{}`) -> ContextType(`{}`): `{}` \
+                    is not an object type defined in your GraphQL schema.",
+                    &graphql_type_name,
+                    context_type.to_token_stream(),
+                    graphql_type_name,
+                )));
+            }
+        }
+
+        // All entries in sync_resolvers should refer to an actual object type
+        // (and field) in the schema
+        for SyncResolver { type_ident, field_ident } in self.sync_resolvers.iter() {
+            let graphql_type_name = type_ident.to_string();
+            let obj_type =
+                if let Some(obj_type) = schema_info.obj_types.get(&graphql_type_name) {
+                    obj_type
+                } else {
+                    return Err(CodegenError::UndefinedGraphQLType(format!(
+                        "Error in `sync_resolvers`: `{}` is not an object type defined in \
+                        your GraphQL schema.",
+                        graphql_type_name,
+                    )));
+                };
+
+            if let Some(field_ident) = field_ident {
+                if !obj_type.fields.iter().any(|field| *field_ident == field.name) {
+                    return Err(CodegenError::UndefinedGraphQLType(format!(
+                        "Error in `sync_resolvers`: `{}.{}` is not a field defined in your \
+                        GraphQL schema.",
+                        graphql_type_name,
+                        field_ident,
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_sync_resolver(&self, graphql_type_name: &String, field_name: &String) -> bool {
+        self.sync_resolvers.iter().any(|SyncResolver { type_ident, field_ident }| {
+            *type_ident == graphql_type_name && match field_ident {
+                Some(field_ident) => *field_ident == field_name,
+                None => true,
+            }
+        })
+    }
+
+    pub fn graphql_scalar_name_to_rust_type(&self, graphql_name: &String) -> Option<&syn::Type> {
+        self.scalar_types.get(graphql_name)
+    }
+
+    pub fn has_rust_type(&self, graphql_name: &String) -> bool {
+        self.rust_types.contains_key(graphql_name)
+    }
+
+    /**
+     * CodegenOptions::validate() ensures every type the schema refers to is
+     * both defined and mapped, so the fallback here (a Rust type named the
+     * same as the GraphQL type) only kicks in if codegen is asked about a type
+     * that validation didn't cover. That leaves rustc to report the unknown
+     * type rather than the macro panicking.
+     */
+    pub fn graphql_type_name_to_rust_type(&self, graphql_name: &String) -> syn::Type {
+        match self.rust_types.get(graphql_name) {
+            Some(rust_type) => rust_type.clone(),
+            None => {
+                let ident = syn::Ident::new(graphql_name.as_str(), proc_macro2::Span::call_site());
+                syn::parse_quote! { #ident }
+            },
+        }
+    }
+}
+
+/**
+ * Checks that every type referenced by the schema -- the root operation types,
+ * field, argument, and input field types, union members, and implemented
+ * interfaces -- is either a built-in scalar or defined in the schema.
+ */
+fn validate_type_references<'a>(schema_info: &SchemaInfo<'a>) -> Result<(), CodegenError> {
+    use graphql_parser::schema::Type;
+
+    let is_defined = |name: &String| {
+        ["Int", "Float", "String", "Boolean", "ID"].contains(&name.as_str())
+            || schema_info.enum_types.contains_key(name)
+            || schema_info.input_obj_types.contains_key(name)
+            || schema_info.interface_types.contains_key(name)
+            || schema_info.obj_types.contains_key(name)
+            || schema_info.scalar_types.contains_key(name)
+            || schema_info.union_types.contains_key(name)
+    };
+    let check = |name: &String, referenced_by: String, pos: graphql_parser::Pos| {
+        if is_defined(name) {
+            Ok(())
+        } else {
+            Err(CodegenError::UndefinedTypeReference {
+                name: name.clone(),
+                referenced_by,
+                pos,
+            })
+        }
+    };
+    fn named_type<'t, 'a>(field_type: &'t Type<'a, String>) -> &'t String {
+        match field_type {
+            Type::NamedType(name) => name,
+            Type::ListType(inner_type) | Type::NonNullType(inner_type) => named_type(inner_type),
+        }
+    }
+    let check_fields = |type_name: &String, fields: &Vec<graphql_parser::schema::Field<'a, String>>| {
+        for field in fields.iter() {
+            let field_path = format!("{}.{}", type_name, field.name);
+            check(named_type(&field.field_type), field_path.clone(), field.position)?;
+            for argument in field.arguments.iter() {
+                check(
+                    named_type(&argument.value_type),
+                    format!("{}({}:)", field_path, argument.name),
+                    argument.position,
+                )?;
+            }
+        }
+        Ok(())
+    };
+
+    let schema_def = &schema_info.schema_def;
+    let root_operation_types = [
+        ("query", &schema_def.query),
+        ("mutation", &schema_def.mutation),
+        ("subscription", &schema_def.subscription),
+    ];
+    for (operation_name, type_name) in root_operation_types {
+        if let Some(type_name) = type_name {
+            if !schema_info.obj_types.contains_key(type_name) {
+                return Err(CodegenError::UndefinedTypeReference {
+                    name: type_name.clone(),
+                    referenced_by: format!("schema.{}", operation_name),
+                    pos: schema_def.position,
+                });
+            }
+        }
+    }
+
+    // Sorted so that the error reported is the first one in the schema
+    let mut obj_types = schema_info.obj_types.values().collect::<Vec<_>>();
+    obj_types.sort_by_key(|obj_type| obj_type.position);
+    for obj_type in obj_types {
+        check_fields(&obj_type.name, &obj_type.fields)?;
+        for interface_name in obj_type.implements_interfaces.iter() {
+            check(interface_name, obj_type.name.clone(), obj_type.position)?;
+        }
+    }
+
+    let mut interface_types = schema_info.interface_types.values().collect::<Vec<_>>();
+    interface_types.sort_by_key(|interface_type| interface_type.position);
+    for interface_type in interface_types {
+        check_fields(&interface_type.name, &interface_type.fields)?;
+        for interface_name in interface_type.implements_interfaces.iter() {
+            check(interface_name, interface_type.name.clone(), interface_type.position)?;
+        }
+    }
+
+    let mut input_obj_types = schema_info.input_obj_types.values().collect::<Vec<_>>();
+    input_obj_types.sort_by_key(|input_obj_type| input_obj_type.position);
+    for input_obj_type in input_obj_types {
+        for field in input_obj_type.fields.iter() {
+            check(
+                named_type(&field.value_type),
+                format!("{}.{}", input_obj_type.name, field.name),
+                field.position,
+            )?;
+        }
+    }
+
+    let mut union_types = schema_info.union_types.values().collect::<Vec<_>>();
+    union_types.sort_by_key(|union_type| union_type.position);
+    for union_type in union_types {
+        for member_name in union_type.types.iter() {
+            check(member_name, union_type.name.clone(), union_type.position)?;
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Checks that every default value in the schema -- on field arguments and
+ * input object fields -- is valid for its type, so that a bad default is
+ * reported here rather than as a panic in generate_default_value()'s
+ * conversion the first time it's used.
+ *
+ * Custom scalars accept any literal since only their Rust type knows how to
+ * parse them.
+ */
+fn validate_default_values<'a>(schema_info: &SchemaInfo<'a>) -> Result<(), CodegenError> {
+    let check = |input_value: &graphql_parser::schema::InputValue<'a, String>, referenced_by: String| {
+        match &input_value.default_value {
+            Some(default_value) => {
+                check_default_value(schema_info, default_value, &input_value.value_type).map_err(
+                    |reason| CodegenError::InvalidDefaultValue {
+                        default_value: default_value.to_string(),
+                        referenced_by,
+                        reason,
+                        pos: input_value.position,
+                    }
+                )
+            },
+            None => Ok(()),
+        }
+    };
+    let check_fields = |type_name: &String, fields: &Vec<graphql_parser::schema::Field<'a, String>>| {
+        for field in fields.iter() {
+            for argument in field.arguments.iter() {
+                check(argument, format!("{}.{}({}:)", type_name, field.name, argument.name))?;
+            }
+        }
         Ok(())
+    };
+
+    // Sorted so that the error reported is the first one in the schema
+    let mut obj_types = schema_info.obj_types.values().collect::<Vec<_>>();
+    obj_types.sort_by_key(|obj_type| obj_type.position);
+    for obj_type in obj_types {
+        check_fields(&obj_type.name, &obj_type.fields)?;
+    }
+
+    let mut interface_types = schema_info.interface_types.values().collect::<Vec<_>>();
+    interface_types.sort_by_key(|interface_type| interface_type.position);
+    for interface_type in interface_types {
+        check_fields(&interface_type.name, &interface_type.fields)?;
+    }
+
+    let mut input_obj_types = schema_info.input_obj_types.values().collect::<Vec<_>>();
+    input_obj_types.sort_by_key(|input_obj_type| input_obj_type.position);
+    for input_obj_type in input_obj_types {
+        for field in input_obj_type.fields.iter() {
+            check(field, format!("{}.{}", input_obj_type.name, field.name))?;
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Checks a (constant) GraphQL value against the type it's a default for,
+ * following GraphQL's input coercion rules. Returns why it's invalid if it
+ * isn't.
+ */
+fn check_default_value<'a>(
+    schema_info: &SchemaInfo<'a>,
+    value: &graphql_parser::query::Value<'a, String>,
+    value_type: &graphql_parser::schema::Type<'a, String>,
+) -> Result<(), String> {
+    use graphql_parser::query::Value;
+    use graphql_parser::schema::Type;
+
+    let invalid = || Err(format!("`{}` is not a valid `{}`", value, value_type));
+    match (value_type, value) {
+        (_, Value::Variable(_)) => Err(String::from("variables can't be used in a schema")),
+        (Type::NonNullType(_), Value::Null) => invalid(),
+        (Type::NonNullType(inner_type), _) => check_default_value(schema_info, value, inner_type),
+        (_, Value::Null) => Ok(()),
+
+        (Type::ListType(item_type), Value::List(items)) => items.iter().try_for_each(
+            |item| check_default_value(schema_info, item, item_type)
+        ),
+        // A single value is coerced to a list of one
+        (Type::ListType(item_type), _) => check_default_value(schema_info, value, item_type),
+
+        (Type::NamedType(type_name), _) => match (type_name.as_str(), value) {
+            ("Int", Value::Int(num)) => match num.as_i64().map(i32::try_from) {
+                Some(Ok(_)) => Ok(()),
+                _ => Err(format!("`{}` doesn't fit in a 32-bit `Int`", value)),
+            },
+            ("Float", Value::Int(_) | Value::Float(_))
+            | ("String", Value::String(_))
+            | ("Boolean", Value::Boolean(_))
+            | ("ID", Value::String(_) | Value::Int(_)) => Ok(()),
+            ("Int" | "Float" | "String" | "Boolean" | "ID", _) => invalid(),
+
+            _ if schema_info.scalar_types.contains_key(type_name) => Ok(()),
+
+            _ if schema_info.enum_types.contains_key(type_name) => {
+                let enum_type = &schema_info.enum_types[type_name];
+                match value {
+                    Value::Enum(enum_value)
+                        if enum_type.values.iter().any(|value| value.name == *enum_value) => Ok(()),
+                    Value::Enum(enum_value) => Err(format!(
+                        "`{}` is not a value of enum `{}`",
+                        enum_value,
+                        type_name,
+                    )),
+                    _ => invalid(),
+                }
+            },
+
+            _ if schema_info.input_obj_types.contains_key(type_name) => {
+                let input_obj_type = &schema_info.input_obj_types[type_name];
+                let fields = match value {
+                    Value::Object(fields) => fields,
+                    _ => return invalid(),
+                };
+                for (field_name, field_value) in fields.iter() {
+                    match input_obj_type.fields.iter().find(|field| field.name == *field_name) {
+                        Some(field) => check_default_value(schema_info, field_value, &field.value_type)?,
+                        None => return Err(format!(
+                            "`{}` is not a field of input type `{}`",
+                            field_name,
+                            type_name,
+                        )),
+                    }
+                }
+                let missing_field = input_obj_type.fields.iter().find(|field| {
+                    matches!(field.value_type, Type::NonNullType(_))
+                        && field.default_value.is_none()
+                        && !fields.contains_key(&field.name)
+                });
+                match missing_field {
+                    Some(field) => Err(format!(
+                        "required field `{}` of input type `{}` is missing",
+                        field.name,
+                        type_name,
+                    )),
+                    None => Ok(()),
+                }
+            },
+
+            // Objects, interfaces and unions aren't input types, which rustc
+            // reports when they're used as one
+            _ => Ok(()),
+        },
     }
+}
+
+/**
+ * GraphQL enum values are conventionally SCREAMING_SNAKE_CASE while Rust enum
+ * variants are CamelCase, so `NEW_HOPE` maps to `NewHope`.
+ */
+fn screaming_snake_case_to_camel_case(name: &str) -> String {
+    name.split('_').filter(|part| !part.is_empty()).map(|part| {
+        let mut chars = part.chars();
+        match chars.next() {
+            Some(first) => {
+                first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect::<String>()
+            },
+            None => String::new(),
+        }
+    }).collect()
+}
+
+/**
+ * Parses the braced form of the `context_type` option:
+ *
+ *   context_type: {
+ *     default: AppCtx,
+ *     Admin*: AdminCtx,
+ *     Viewer: ViewerCtx,
+ *   }
+ */
+fn parse_per_type_context_type(input: syn::parse::ParseStream) -> syn::Result<ContextType> {
+    let context_types_tokens;
+    let braces = syn::braced!(context_types_tokens in input);
 
-    pub fn graphql_type_name_to_rust_type(&self, graphql_name: &String) -> &syn::Ident {
-        //if let Some(type_map) = &self.rust_types {
-            // Unwrap is safe here since we've already validated the presence of
-            // all types in CodegenOptions::validate()
-            if let None = self.rust_types.get(graphql_name) {
-                panic!("No graphql->rust entry for {}", graphql_name);
+    let mut default = None::<syn::Type>;
+    let mut type_contexts = vec![];
+    while !context_types_tokens.is_empty() {
+        let key_ident = context_types_tokens.parse::<syn::Ident>()?;
+        let is_prefix = context_types_tokens.peek(syn::Token![*]);
+        if is_prefix {
+            context_types_tokens.parse::<syn::Token![*]>()?;
+        }
+        context_types_tokens.parse::<syn::Token![:]>()?;
+        let context_type = context_types_tokens.parse::<syn::Type>()?;
+
+        if key_ident == "default" && !is_prefix {
+            if let Some(_) = default {
+                return Err(syn::parse::Error::new(
+                    key_ident.span(),
+                    "`default` context type specified more than once!",
+                ));
             }
-            self.rust_types.get(graphql_name).unwrap()
-        /*} else {
-            graphql_name.to_string()
-        }*/
+            let _ = default.insert(context_type);
+        } else {
+            type_contexts.push((
+                crate::TypeNamePattern { name: key_ident, is_prefix },
+                context_type,
+            ));
+        }
+
+        if context_types_tokens.peek(syn::Token![,]) {
+            context_types_tokens.parse::<syn::Token![,]>()?;
+        }
     }
+
+    let default =
+        if let Some(default) = default {
+            default
+        } else {
+            return Err(syn::parse::Error::new(
+                braces.span.join(),
+                "A `default` context type is required when specifying per-type context types.",
+            ));
+        };
+
+    Ok(ContextType::PerType { default, type_contexts })
+}
+
+struct SyncResolver {
+    type_ident: syn::Ident,
+    field_ident: Option<syn::Ident>,
 }
 
 enum MapperToken {
     FatArrow,
     SkinnyArrow,
 }
+impl MapperToken {
+    /**
+     * Parse the arrow between the two sides of a mapping entry, remembering
+     * whichever arrow was used first so that all later entries (in any
+     * mapping block) must use the same one.
+     */
+    fn parse(
+        input: syn::parse::ParseStream,
+        mapping_arrow_token: &mut Option<MapperToken>,
+    ) -> syn::Result<()> {
+        match mapping_arrow_token {
+            Some(MapperToken::SkinnyArrow) => {
+                input.parse::<syn::Token![->]>()?;
+            },
+            Some(MapperToken::FatArrow) => {
+                input.parse::<syn::Token![=>]>()?;
+            },
+            None => {
+                if input.peek(syn::Token![->]) {
+                    let _ = mapping_arrow_token.insert(MapperToken::SkinnyArrow);
+                    input.parse::<syn::Token![->]>()?;
+                } else {
+                    let _ = mapping_arrow_token.insert(MapperToken::FatArrow);
+                    input.parse::<syn::Token![=>]>()?;
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_generate(schema: &str, options: &str) -> Result<proc_macro2::TokenStream, CodegenError> {
+        let schema_file = SchemaFile {
+            path: String::from("schema.graphqls"),
+            source: String::from(schema),
+            span: proc_macro2::Span::call_site(),
+        };
+        let options = syn::parse_str::<CodegenOptions>(options).unwrap();
+        let root_node_ident = syn::Ident::new("Schema", proc_macro2::Span::call_site());
+        Codegen::new(root_node_ident, schema_file, options)?.to_tokens()
+    }
+
+    fn generate(schema: &str, options: &str) -> syn::File {
+        match try_generate(schema, options) {
+            Ok(tokens) => syn::parse2::<syn::File>(tokens).unwrap(),
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    fn generate_error(schema: &str, options: &str) -> String {
+        match try_generate(schema, options) {
+            Ok(_) => panic!("expected an error"),
+            // Only the first line; the rest is the schema snippet
+            Err(e) => e.message(None).lines().next().unwrap().to_string(),
+        }
+    }
+
+    fn trait_names(file: &syn::File) -> Vec<String> {
+        file.items.iter().filter_map(|item| match item {
+            syn::Item::Trait(item_trait) => Some(item_trait.ident.to_string()),
+            _ => None,
+        }).collect()
+    }
+
+    #[test]
+    fn resolver_trait_names_are_unique() {
+        let file = generate(
+            concat!(
+                "type Query { order: Order orderItem: OrderItem }\n",
+                "type Order { itemCount: Int id: ID Id: ID }\n",
+                "type OrderItem { count: Int }\n",
+            ),
+            "types: { Query -> Query, Order -> Order, OrderItem -> OrderItem }",
+        );
+        let mut names = trait_names(&file);
+        let total = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), total, "duplicate trait names in {:?}", names);
+        assert!(names.contains(&String::from("__Resolver_5Order_9itemCount")));
+        assert!(names.contains(&String::from("__Resolver_9OrderItem_5count")));
+        assert!(names.contains(&String::from("__Resolver_5Order_2id")));
+        assert!(names.contains(&String::from("__Resolver_5Order_2Id")));
+    }
+
+    #[test]
+    fn interface_impls_convert_narrower_field_types() {
+        let file = generate(
+            concat!(
+                "type Query { node: Node }\n",
+                "interface Node { self: Node friends: [Node!] }\n",
+                "type User implements Node { self: User! friends: [User!]! }\n",
+            ),
+            "types: { Query -> Query, Node -> Node, User -> User }",
+        );
+        let interface_impl = file.items.iter().find_map(|item| match item {
+            syn::Item::Impl(item_impl) if item_impl.trait_.as_ref().map_or(false, |(_, path, _)| {
+                path.is_ident("__NodeInterface")
+            }) => Some(quote::quote! { #item_impl }.to_string()),
+            _ => None,
+        }).expect("no impl of __NodeInterface");
+        assert!(
+            interface_impl.contains("Some (__NodeWrapper :: from (resolved))"),
+            "`self` isn't converted to the interface's type in {}", interface_impl,
+        );
+        assert!(
+            interface_impl.contains(
+                "Some (resolved . into_iter () . map (| item | __NodeWrapper :: from (item)) . collect :: < Vec < _ > > ())"
+            ),
+            "`friends` isn't converted to the interface's type in {}", interface_impl,
+        );
+    }
+
+    #[test]
+    fn invalid_default_values_are_rejected() {
+        let types = "types: { Query -> Query, E -> E }";
+        let error = |schema: &str| generate_error(
+            &format!("type Query {{ {} }}\nenum E {{ ASC DESC }}\n", schema),
+            types,
+        );
+        assert_eq!(
+            error("f(o: E = BOGUS): Int"),
+            "Invalid default value `BOGUS` for `Query.f(o:)` at schema.graphqls:1:16: `BOGUS` is not a value of enum `E`.",
+        );
+        assert_eq!(
+            error("f(n: Int = \"5\"): Int"),
+            "Invalid default value `\"5\"` for `Query.f(n:)` at schema.graphqls:1:16: `\"5\"` is not a valid `Int`.",
+        );
+        assert_eq!(
+            error("f(n: Int! = null): Int"),
+            "Invalid default value `null` for `Query.f(n:)` at schema.graphqls:1:16: `null` is not a valid `Int!`.",
+        );
+        assert_eq!(
+            error("f(n: [E] = [ASC, 1]): Int"),
+            "Invalid default value `[ASC, 1]` for `Query.f(n:)` at schema.graphqls:1:16: `1` is not a valid `E`.",
+        );
+
+        let input_error = |default: &str| generate_error(
+            &format!(
+                "type Query {{ f(i: I = {}): Int }}\ninput I {{ name: String! limit: Int = 5 }}\n",
+                default,
+            ),
+            "types: { Query -> Query }",
+        );
+        assert_eq!(
+            input_error("{name: \"x\", bogus: 1}"),
+            "Invalid default value `{bogus: 1, name: \"x\"}` for `Query.f(i:)` at schema.graphqls:1:16: `bogus` \
+            is not a field of input type `I`.",
+        );
+        assert_eq!(
+            input_error("{limit: 1}"),
+            "Invalid default value `{limit: 1}` for `Query.f(i:)` at schema.graphqls:1:16: required field `name` \
+            of input type `I` is missing.",
+        );
+        assert_eq!(
+            generate_error(
+                "type Query { f: Int }\ninput I { limit: Int = 1.5 }\n",
+                "types: { Query -> Query }",
+            ),
+            "Invalid default value `1.5` for `I.limit` at schema.graphqls:2:11: `1.5` is not a valid `Int`.",
+        );
+    }
+
+    #[test]
+    fn valid_default_values_are_accepted() {
+        generate(
+            concat!(
+                "type Query {\n",
+                "  f(e: E = ASC, es: [E!] = ASC, n: Float = 1, id: ID = 1, i: I = {name: \"x\"}): Int\n",
+                "}\n",
+                "enum E { ASC DESC }\n",
+                "input I { name: String! limit: Int = 5 tags: [String] = null }\n",
+            ),
+            "types: { Query -> Query, E -> E }",
+        );
+    }
+}
@@ -0,0 +1,174 @@
+/**
+ * Arguments to the `#[juniper_schema::field_resolvers(...)]` attribute.
+ *
+ * e.g. The stuff between the parens in
+ *
+ *    #[juniper_schema::field_resolvers(<<<<stuff here>>>>)]
+ *    impl User {
+ *        [...]
+ *    }
+ */
+pub struct FieldResolversArgs {
+    schema_module: syn::Path,
+    graphql_type: Option<syn::Ident>,
+}
+impl syn::parse::Parse for FieldResolversArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut schema_module = None::<syn::Path>;
+        let mut graphql_type = None::<syn::Ident>;
+
+        while !input.is_empty() {
+            let arg_key = input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+            match arg_key.to_string().as_str() {
+                "schema_module" => {
+                    if let Some(_) = schema_module {
+                        return Err(syn::parse::Error::new(
+                            arg_key.span(),
+                            "`schema_module` specified more than once!",
+                        ));
+                    }
+                    let _ = schema_module.insert(input.parse::<syn::Path>()?);
+                },
+
+                "graphql_type" => {
+                    if let Some(_) = graphql_type {
+                        return Err(syn::parse::Error::new(
+                            arg_key.span(),
+                            "`graphql_type` specified more than once!",
+                        ));
+                    }
+                    let _ = graphql_type.insert(input.parse::<syn::Ident>()?);
+                },
+
+                other => {
+                    return Err(syn::parse::Error::new(
+                        arg_key.span(),
+                        format!("Unexpected argument: `{}`", other),
+                    ));
+                }
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        let schema_module =
+            if let Some(schema_module) = schema_module {
+                schema_module
+            } else {
+                return Err(syn::parse::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "Missing required argument `schema_module`. \
+                    #[field_resolvers] implements the `<Type>FieldResolvers` \
+                    trait that `juniper_schema::from_file!()` generates for \
+                    each GraphQL object type, and it needs the path of the \
+                    module that `from_file!()` is invoked in to find that \
+                    trait (e.g. `#[field_resolvers(schema_module=crate::schema)]`).",
+                ));
+            };
+
+        Ok(FieldResolversArgs {
+            schema_module,
+            graphql_type,
+        })
+    }
+}
+
+/**
+ * Rewrites an inherent impl block of field resolvers into an impl of the
+ * resolver trait that `juniper_schema::from_file!()` generated for the
+ * corresponding GraphQL object type.
+ *
+ * Given:
+ *
+ *    #[field_resolvers(schema_module=super::schema)]
+ *    impl User {
+ *        pub async fn resolve_id(&self, ctx: &Context) -> Option<juniper::ID> {
+ *            [...]
+ *        }
+ *    }
+ *
+ * produces:
+ *
+ *    #[async_trait::async_trait]
+ *    impl super::schema::UserFieldResolvers for User {
+ *        async fn resolve_id(&self, ctx: &Context) -> Option<juniper::ID> {
+ *            [...]
+ *        }
+ *    }
+ *
+ * The trait is named after the GraphQL object type, which is assumed to share
+ * its name with the Rust type unless `graphql_type=...` says otherwise.
+ */
+pub struct ImplToTraitMapper {
+    args: FieldResolversArgs,
+    item_impl: syn::ItemImpl,
+}
+impl ImplToTraitMapper {
+    pub fn new(args: FieldResolversArgs, item_impl: syn::ItemImpl) -> syn::Result<Self> {
+        if let Some((_, trait_path, _)) = &item_impl.trait_ {
+            return Err(syn::parse::Error::new_spanned(
+                trait_path,
+                "#[field_resolvers] must be placed on an inherent impl block \
+                (e.g. `impl User { ... }`). It adds the resolver trait itself.",
+            ));
+        }
+
+        Ok(ImplToTraitMapper {
+            args,
+            item_impl,
+        })
+    }
+
+    fn graphql_type_ident(&self) -> syn::Result<syn::Ident> {
+        if let Some(graphql_type) = &self.args.graphql_type {
+            return Ok(graphql_type.clone());
+        }
+
+        match &*self.item_impl.self_ty {
+            syn::Type::Path(type_path) if type_path.qself.is_none() => {
+                // Unwrap is safe here since a path always has at least one
+                // segment
+                Ok(type_path.path.segments.last().unwrap().ident.clone())
+            },
+            self_ty => Err(syn::parse::Error::new_spanned(
+                self_ty,
+                "Unable to infer the GraphQL object type for this impl block. \
+                Specify it with `graphql_type=...` in #[field_resolvers(...)].",
+            )),
+        }
+    }
+
+    pub fn to_tokens(self) -> syn::Result<proc_macro2::TokenStream> {
+        let graphql_type_ident = self.graphql_type_ident()?;
+        let resolver_trait_ident = syn::Ident::new(
+            format!("{}FieldResolvers", graphql_type_ident).as_str(),
+            graphql_type_ident.span(),
+        );
+        let schema_module = &self.args.schema_module;
+
+        let mut item_impl = self.item_impl;
+
+        // Trait impl items can't have a visibility, but it's natural to write
+        // `pub` on resolvers in what looks like an inherent impl block
+        for item in item_impl.items.iter_mut() {
+            if let syn::ImplItem::Fn(method) = item {
+                method.vis = syn::Visibility::Inherited;
+            }
+        }
+
+        let resolver_trait_path = syn::parse_quote! {
+            #schema_module::#resolver_trait_ident
+        };
+        item_impl.trait_ = Some((None, resolver_trait_path, Default::default()));
+        item_impl.attrs.push(syn::parse_quote! {
+            #[async_trait::async_trait]
+        });
+
+        Ok(quote::quote! {
+            #item_impl
+        })
+    }
+}
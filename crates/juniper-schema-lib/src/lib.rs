@@ -3,53 +3,464 @@
 pub mod codegen;
 pub mod codegen2;
 pub mod codegen3;
+pub mod impl_to_trait_mapper;
+pub mod naming;
 pub mod schema_info;
 
 pub use codegen::CodegenFromFile;
 pub use codegen2::SchemaFromFile2;
 pub use codegen3::SchemaFromFile3;
+pub use impl_to_trait_mapper::FieldResolversArgs;
+pub use impl_to_trait_mapper::ImplToTraitMapper;
 
 pub enum ContextType {
     Global(syn::Type),
-    // TODO: It can be useful to specify a different context type for each GraphQL type. At some
-    //       point we could allow this with some type of mapping syntax for the context arg.
+
+    /**
+     * `context_type: { default: AppCtx, Admin*: AdminCtx }`
+     *
+     * juniper executes an entire schema with a single context type (the
+     * `default` one here). Resolvers on GraphQL types whose name matches one of
+     * the other entries are instead handed the sub-context they asked for,
+     * which is extracted from the default context via `juniper::FromContext`.
+     */
+    PerType {
+        default: syn::Type,
+        type_contexts: Vec<(TypeNamePattern, syn::Type)>,
+    },
+}
+impl ContextType {
+    /**
+     * The context type that juniper executes the schema with.
+     */
+    pub fn juniper_context_type(&self) -> &syn::Type {
+        match self {
+            ContextType::Global(context_type) => context_type,
+            ContextType::PerType { default, .. } => default,
+        }
+    }
+
+    /**
+     * The sub-context type that resolvers on the given GraphQL type receive
+     * instead of the juniper context (if any). When more than one entry
+     * matches, the first one listed wins.
+     */
+    pub fn type_context_type(&self, graphql_type_name: &str) -> Option<&syn::Type> {
+        match self {
+            ContextType::Global(_) => None,
+            ContextType::PerType { type_contexts, .. } => type_contexts.iter().find(
+                |(pattern, _)| pattern.matches(graphql_type_name)
+            ).map(|(_, context_type)| context_type),
+        }
+    }
+}
+
+/**
+ * Either an exact GraphQL type name (`Admin`) or a name prefix followed by a
+ * `*` (`Admin*`).
+ */
+pub struct TypeNamePattern {
+    pub name: syn::Ident,
+    pub is_prefix: bool,
+}
+impl TypeNamePattern {
+    pub fn matches(&self, graphql_type_name: &str) -> bool {
+        let name = self.name.to_string();
+        if self.is_prefix {
+            graphql_type_name.starts_with(name.as_str())
+        } else {
+            graphql_type_name == name
+        }
+    }
+}
+
+/**
+ * The schema file that a macro invocation reads from, kept around so that
+ * errors can point at (and quote) the offending part of it.
+ */
+#[derive(Clone, Debug)]
+pub struct SchemaFile {
+    /**
+     * The path as written in the macro invocation.
+     */
+    pub path: String,
+    pub source: String,
+    pub span: proc_macro2::Span,
+}
+impl SchemaFile {
+    /**
+     * `schema.graphqls:14:1`
+     */
+    pub fn location(&self, pos: &graphql_parser::Pos) -> String {
+        format!("{}:{}:{}", self.path, pos.line, pos.column)
+    }
+
+    /**
+     * The line of the schema at `pos` with a caret under the column, formatted
+     * the way rustc quotes source lines:
+     *
+     *    --> schema.graphqls:14:1
+     *     |
+     *  14 | directive @cached on FIELD_DEFINITION
+     *     | ^
+     */
+    pub fn snippet(&self, pos: &graphql_parser::Pos) -> Option<String> {
+        let line = self.source.lines().nth(pos.line.checked_sub(1)?)?;
+        let line_num = pos.line.to_string();
+        let gutter = " ".repeat(line_num.len());
+
+        // Keep any tabs in the line's indentation so the caret still lines up
+        let caret_indent = line.chars().take(pos.column.saturating_sub(1)).map(
+            |c| if c == '\t' { '\t' } else { ' ' }
+        ).collect::<String>();
+
+        Some(format!(
+            "{gutter}--> {location}\n{gutter} |\n{line_num} | {line}\n{gutter} | {caret_indent}^",
+            gutter = gutter,
+            location = self.location(pos),
+            line_num = line_num,
+            line = line,
+            caret_indent = caret_indent,
+        ))
+    }
 }
 
 #[derive(Debug)]
 pub enum CodegenError {
+    DuplicateExtensionMember {
+        type_name: String,
+        member_name: String,
+        first: graphql_parser::Pos,
+        second: graphql_parser::Pos,
+    },
+    InSchemaFile(Box<CodegenError>, SchemaFile),
+    InvalidDefaultValue {
+        default_value: String,
+        referenced_by: String,
+        reason: String,
+        pos: graphql_parser::Pos,
+    },
     IoError(std::io::Error, proc_macro2::Span),
     MultipleEnumTypeDefinitions {
         first: graphql_parser::Pos,
         second: graphql_parser::Pos,
     },
+    MultipleInputObjectTypeDefinitions {
+        first: graphql_parser::Pos,
+        second: graphql_parser::Pos,
+    },
+    MultipleInterfaceTypeDefinitions {
+        first: graphql_parser::Pos,
+        second: graphql_parser::Pos,
+    },
     MultipleObjectTypeDefinitions {
         first: graphql_parser::Pos,
         second: graphql_parser::Pos,
     },
+    MultipleScalarTypeDefinitions {
+        first: graphql_parser::Pos,
+        second: graphql_parser::Pos,
+    },
     MultipleSchemaDefinitions {
         first: graphql_parser::Pos,
         second: graphql_parser::Pos,
     },
+    MultipleUnionTypeDefinitions {
+        first: graphql_parser::Pos,
+        second: graphql_parser::Pos,
+    },
     NoQueryDefinitionFound,
     NoSchemaDefinitionFound,
     SchemaParseError(graphql_parser::schema::ParseError),
+    UndefinedExtendedType {
+        name: String,
+        pos: graphql_parser::Pos,
+    },
     UndefinedGraphQLType(String),
+    UndefinedTypeReference {
+        name: String,
+        referenced_by: String,
+        pos: graphql_parser::Pos,
+    },
+    UnmappedGraphQLScalar(String),
+    UnmappedGraphQLType(String, proc_macro2::Span),
+    UnsupportedDefinition {
+        kind: &'static str,
+        name: String,
+        pos: graphql_parser::Pos,
+    },
 }
 impl CodegenError {
-    pub fn to_compile_error(&self) -> proc_macro2::TokenStream {
-        let default_span = proc_macro2::Span::call_site();
-        let error_strlit = match self {
-            CodegenError::UndefinedGraphQLType(msg) => {
-                syn::LitStr::new(msg.as_str(), default_span)
-            },
-            _other => {
-                let err = format!("Error generating code for GraphQL schema: {:?}", self);
-                syn::LitStr::new(err.as_str(), default_span)
-            }
+    /**
+     * Attaches the schema file that the error came from so that it can be
+     * reported with `path:line:col` and a snippet of the offending line.
+     */
+    pub fn in_schema_file(self, schema_file: &SchemaFile) -> Self {
+        match self {
+            CodegenError::InSchemaFile(..) | CodegenError::IoError(..) => self,
+            other => CodegenError::InSchemaFile(Box::new(other), schema_file.clone()),
+        }
+    }
+
+    /**
+     * Where in the schema the error is (if it's about a specific spot in the
+     * schema).
+     */
+    fn pos(&self) -> Option<graphql_parser::Pos> {
+        match self {
+            CodegenError::InSchemaFile(error, _) => error.pos(),
+            CodegenError::DuplicateExtensionMember { second, .. }
+            | CodegenError::MultipleEnumTypeDefinitions { second, .. }
+            | CodegenError::MultipleInputObjectTypeDefinitions { second, .. }
+            | CodegenError::MultipleInterfaceTypeDefinitions { second, .. }
+            | CodegenError::MultipleObjectTypeDefinitions { second, .. }
+            | CodegenError::MultipleScalarTypeDefinitions { second, .. }
+            | CodegenError::MultipleSchemaDefinitions { second, .. }
+            | CodegenError::MultipleUnionTypeDefinitions { second, .. } => Some(*second),
+            CodegenError::InvalidDefaultValue { pos, .. }
+            | CodegenError::UndefinedExtendedType { pos, .. }
+            | CodegenError::UndefinedTypeReference { pos, .. }
+            | CodegenError::UnsupportedDefinition { pos, .. } => Some(*pos),
+
+            // graphql_parser's ParseError only exposes its position through
+            // its message: "schema parse error: Parse error at 3:14\n..."
+            CodegenError::SchemaParseError(e) => {
+                let message = e.to_string();
+                let (_, pos_str) = message.split_once(" at ")?;
+                let pos_str = pos_str.split_whitespace().next()?;
+                let (line, column) = pos_str.split_once(':')?;
+                Some(graphql_parser::Pos {
+                    line: line.parse().ok()?,
+                    column: column.parse().ok()?,
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /**
+     * Errors about the macro invocation itself (rather than the schema) point
+     * at the relevant part of the invocation.
+     */
+    fn span(&self) -> Option<proc_macro2::Span> {
+        match self {
+            CodegenError::InSchemaFile(error, schema_file) => {
+                Some(error.span().unwrap_or(schema_file.span))
+            },
+            CodegenError::IoError(_, span)
+            | CodegenError::UnmappedGraphQLType(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+
+    fn message(&self, schema_file: Option<&SchemaFile>) -> String {
+        let location = |pos: &graphql_parser::Pos| match schema_file {
+            Some(schema_file) => schema_file.location(pos),
+            None => pos.to_string(),
         };
+        let multiple_definitions = |kind: &str, first, second| format!(
+            "Multiple {} definitions found: {} conflicts with the definition at {}.",
+            kind,
+            location(second),
+            location(first),
+        );
+
+        match self {
+            CodegenError::InSchemaFile(error, schema_file) => {
+                let message = error.message(Some(schema_file));
+                match error.pos().and_then(|pos| schema_file.snippet(&pos)) {
+                    Some(snippet) => format!("{}\n{}", message, snippet),
+                    None => message,
+                }
+            },
+            CodegenError::UndefinedGraphQLType(msg)
+            | CodegenError::UnmappedGraphQLScalar(msg)
+            | CodegenError::UnmappedGraphQLType(msg, _) => msg.clone(),
+            CodegenError::DuplicateExtensionMember { type_name, member_name, first, second } => {
+                format!(
+                    "Error extending `{}` at {}: `{}` is already defined at {}.",
+                    type_name,
+                    location(second),
+                    member_name,
+                    location(first),
+                )
+            },
+            CodegenError::InvalidDefaultValue { default_value, referenced_by, reason, pos } => {
+                format!(
+                    "Invalid default value `{}` for `{}` at {}: {}.",
+                    default_value,
+                    referenced_by,
+                    location(pos),
+                    reason,
+                )
+            },
+            CodegenError::IoError(e, _) => format!("Error reading GraphQL schema file: {}", e),
+            CodegenError::MultipleEnumTypeDefinitions { first, second } => {
+                multiple_definitions("enum type", first, second)
+            },
+            CodegenError::MultipleInputObjectTypeDefinitions { first, second } => {
+                multiple_definitions("input object type", first, second)
+            },
+            CodegenError::MultipleInterfaceTypeDefinitions { first, second } => {
+                multiple_definitions("interface type", first, second)
+            },
+            CodegenError::MultipleObjectTypeDefinitions { first, second } => {
+                multiple_definitions("object type", first, second)
+            },
+            CodegenError::MultipleScalarTypeDefinitions { first, second } => {
+                multiple_definitions("scalar type", first, second)
+            },
+            CodegenError::MultipleSchemaDefinitions { first, second } => {
+                multiple_definitions("schema", first, second)
+            },
+            CodegenError::MultipleUnionTypeDefinitions { first, second } => {
+                multiple_definitions("union type", first, second)
+            },
+            CodegenError::NoQueryDefinitionFound => String::from(
+                "No query type found in your GraphQL schema.",
+            ),
+            CodegenError::NoSchemaDefinitionFound => String::from(
+                "No `schema { ... }` definition or `Query` type found in your GraphQL schema.",
+            ),
+            CodegenError::SchemaParseError(e) => {
+                // The first line of the message is just the position, which we
+                // report ourselves when we know it
+                let message = e.to_string();
+                match (self.pos(), message.split_once('\n')) {
+                    (Some(pos), Some((_, details))) => format!(
+                        "Error parsing GraphQL schema at {}:\n{}",
+                        location(&pos),
+                        details.trim_end(),
+                    ),
+                    _ => format!("Error parsing GraphQL schema: {}", message.trim_end()),
+                }
+            },
+            CodegenError::UndefinedExtendedType { name, pos } => {
+                format!(
+                    "Error extending `{}` at {}: `{}` is not defined in your GraphQL schema.",
+                    name,
+                    location(pos),
+                    name,
+                )
+            },
+            CodegenError::UndefinedTypeReference { name, referenced_by, pos } => {
+                format!(
+                    "Unknown type `{}` referenced by `{}` at {}: `{}` is not defined in your \
+                    GraphQL schema.",
+                    name,
+                    referenced_by,
+                    location(pos),
+                    name,
+                )
+            },
+            CodegenError::UnsupportedDefinition { kind, name, pos } => {
+                format!(
+                    "{} definitions are not supported yet: `{}` ({})",
+                    kind,
+                    name,
+                    location(pos),
+                )
+            },
+        }
+    }
+
+    pub fn to_compile_error(&self) -> proc_macro2::TokenStream {
+        let span = self.span().unwrap_or_else(proc_macro2::Span::call_site);
+        let error_strlit = syn::LitStr::new(self.message(None).as_str(), span);
 
-        quote::quote! {
+        quote::quote_spanned! {span=>
             compile_error!(#error_strlit);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_file(source: &str) -> SchemaFile {
+        SchemaFile {
+            path: String::from("schema.graphqls"),
+            source: String::from(source),
+            span: proc_macro2::Span::call_site(),
+        }
+    }
+
+    fn parse_error(source: &str) -> CodegenError {
+        match graphql_parser::parse_schema::<String>(source) {
+            Ok(_) => panic!("expected a parse error"),
+            Err(e) => CodegenError::SchemaParseError(e),
+        }
+    }
+
+    #[test]
+    fn parse_error_pos() {
+        let error = parse_error("type Query {\n  hello: String\n  oops(: Int\n}\n");
+        assert_eq!(error.pos(), Some(graphql_parser::Pos { line: 3, column: 8 }));
+    }
+
+    #[test]
+    fn parse_error_message() {
+        let source = "type Query {\n  oops(: Int\n}\n";
+        let error = parse_error(source).in_schema_file(&schema_file(source));
+        assert_eq!(error.message(None), concat!(
+            "Error parsing GraphQL schema at schema.graphqls:2:8:\n",
+            "Unexpected `:[Punctuator]`\n",
+            "Expected `Name`\n",
+            " --> schema.graphqls:2:8\n",
+            "  |\n",
+            "2 |   oops(: Int\n",
+            "  |        ^",
+        ));
+    }
+
+    #[test]
+    fn snippet() {
+        let schema_file = schema_file("type Query {\n  hello: String\n}\n");
+        assert_eq!(
+            schema_file.snippet(&graphql_parser::Pos { line: 2, column: 10 }).as_deref(),
+            Some(concat!(
+                " --> schema.graphqls:2:10\n",
+                "  |\n",
+                "2 |   hello: String\n",
+                "  |          ^",
+            )),
+        );
+    }
+
+    #[test]
+    fn snippet_keeps_tab_indentation() {
+        let schema_file = schema_file("type Query {\n\thello: String\n}\n");
+        assert_eq!(
+            schema_file.snippet(&graphql_parser::Pos { line: 2, column: 9 }).as_deref(),
+            Some(concat!(
+                " --> schema.graphqls:2:9\n",
+                "  |\n",
+                "2 | \thello: String\n",
+                "  | \t       ^",
+            )),
+        );
+    }
+
+    #[test]
+    fn snippet_pads_gutter_for_wide_line_numbers() {
+        let source = "\n".repeat(9) + "type Query";
+        let schema_file = schema_file(&source);
+        assert_eq!(
+            schema_file.snippet(&graphql_parser::Pos { line: 10, column: 6 }).as_deref(),
+            Some(concat!(
+                "  --> schema.graphqls:10:6\n",
+                "   |\n",
+                "10 | type Query\n",
+                "   |      ^",
+            )),
+        );
+    }
+
+    #[test]
+    fn snippet_out_of_range() {
+        let schema_file = schema_file("type Query {\n  hello: String\n}\n");
+        assert_eq!(schema_file.snippet(&graphql_parser::Pos { line: 4, column: 1 }), None);
+        assert_eq!(schema_file.snippet(&graphql_parser::Pos { line: 0, column: 1 }), None);
+    }
+}
@@ -0,0 +1,163 @@
+/**
+ * How the names of GraphQL fields and arguments map to the names of the Rust
+ * methods, params (and struct fields) that correspond to them. The GraphQL-facing names always
+ * stay exactly as written in the schema.
+ *
+ * e.g. `rename: snake_case` (the default) or `rename: none`
+ */
+#[derive(Clone, Copy, Default)]
+pub enum RenameStrategy {
+    /**
+     * `createdAt` -> `created_at`
+     */
+    #[default]
+    SnakeCase,
+
+    /**
+     * `createdAt` -> `createdAt`
+     */
+    None,
+}
+impl syn::parse::Parse for RenameStrategy {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let strategy_ident = input.parse::<syn::Ident>()?;
+        match strategy_ident.to_string().as_str() {
+            "snake_case" => Ok(RenameStrategy::SnakeCase),
+            "none" => Ok(RenameStrategy::None),
+            other => Err(syn::parse::Error::new(
+                strategy_ident.span(),
+                format!(
+                    "Unknown rename strategy: `{}` (expected `snake_case` or `none`)",
+                    other,
+                ),
+            )),
+        }
+    }
+}
+impl RenameStrategy {
+    pub fn apply(&self, graphql_name: &str) -> String {
+        match self {
+            RenameStrategy::SnakeCase => camel_case_to_snake_case(graphql_name),
+            RenameStrategy::None => String::from(graphql_name),
+        }
+    }
+
+    /**
+     * The Rust identifier for a GraphQL field or argument (after renaming).
+     */
+    pub fn field_ident(&self, graphql_name: &str, span: proc_macro2::Span) -> syn::Ident {
+        rust_ident(self.apply(graphql_name).as_str(), span)
+    }
+}
+
+/**
+ * GraphQL field and argument names are conventionally camelCase while Rust
+ * params and methods are snake_case, so `firstName` maps to `first_name`.
+ */
+pub fn camel_case_to_snake_case(name: &str) -> String {
+    let mut snake_case = String::with_capacity(name.len());
+    let mut prev_char = None::<char>;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_uppercase() {
+            // Start a new word at a lower->upper transition (`firstName`) or at
+            // the last capital of an acronym that starts a new word (`HTTPServer`)
+            let starts_word = match prev_char {
+                Some(prev) if prev.is_lowercase() || prev.is_ascii_digit() => true,
+                Some(prev) if prev.is_uppercase() => {
+                    chars.peek().is_some_and(|next| next.is_lowercase())
+                },
+                _ => false,
+            };
+            if starts_word && !snake_case.ends_with('_') {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+        prev_char = Some(c);
+    }
+    snake_case
+}
+
+/**
+ * Names that are fine in GraphQL (`type`, `match`, `self`, ...) can't always be
+ * used as-is for a Rust identifier.
+ *
+ * Keywords are escaped as raw identifiers (`r#type`), except for the handful
+ * that can't be raw identifiers either (`self`, `super`, ...), which get a
+ * trailing underscore instead (`self_`).
+ */
+pub fn rust_ident(name: &str, span: proc_macro2::Span) -> syn::Ident {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const",
+        "continue", "do", "dyn", "else", "enum", "extern", "false", "final", "fn",
+        "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+        "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+        "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+        "use", "virtual", "where", "while", "yield",
+    ];
+    const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+    if NON_RAW_KEYWORDS.contains(&name) {
+        syn::Ident::new(format!("{}_", name).as_str(), span)
+    } else if KEYWORDS.contains(&name) {
+        syn::Ident::new_raw(name, span)
+    } else {
+        syn::Ident::new(name, span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident_str(name: &str) -> String {
+        rust_ident(name, proc_macro2::Span::call_site()).to_string()
+    }
+
+    #[test]
+    fn camel_case_to_snake_case_words() {
+        assert_eq!(camel_case_to_snake_case("firstName"), "first_name");
+        assert_eq!(camel_case_to_snake_case("name"), "name");
+        assert_eq!(camel_case_to_snake_case("already_snake"), "already_snake");
+    }
+
+    #[test]
+    fn camel_case_to_snake_case_acronyms() {
+        assert_eq!(camel_case_to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(camel_case_to_snake_case("userID"), "user_id");
+        assert_eq!(camel_case_to_snake_case("parseHTTPResponse"), "parse_http_response");
+    }
+
+    #[test]
+    fn camel_case_to_snake_case_digits() {
+        assert_eq!(camel_case_to_snake_case("field2Name"), "field2_name");
+        assert_eq!(camel_case_to_snake_case("address2"), "address2");
+    }
+
+    #[test]
+    fn rust_ident_escapes_keywords() {
+        assert_eq!(ident_str("type"), "r#type");
+        assert_eq!(ident_str("match"), "r#match");
+        assert_eq!(ident_str("async"), "r#async");
+        assert_eq!(ident_str("name"), "name");
+    }
+
+    #[test]
+    fn rust_ident_suffixes_non_raw_keywords() {
+        assert_eq!(ident_str("self"), "self_");
+        assert_eq!(ident_str("Self"), "Self_");
+        assert_eq!(ident_str("super"), "super_");
+        assert_eq!(ident_str("crate"), "crate_");
+    }
+
+    #[test]
+    fn rename_strategy_field_ident() {
+        let span = proc_macro2::Span::call_site();
+        assert_eq!(RenameStrategy::SnakeCase.field_ident("createdAt", span).to_string(), "created_at");
+        assert_eq!(RenameStrategy::None.field_ident("createdAt", span).to_string(), "createdAt");
+        assert_eq!(RenameStrategy::None.field_ident("type", span).to_string(), "r#type");
+    }
+}
@@ -8,10 +8,26 @@ pub struct SchemaInfo<'a> {
         String,
         graphql_parser::schema::EnumType<'a, String>
     >,
+    pub input_obj_types: HashMap<
+        String,
+        graphql_parser::schema::InputObjectType<'a, String>,
+    >,
+    pub interface_types: HashMap<
+        String,
+        graphql_parser::schema::InterfaceType<'a, String>,
+    >,
     pub obj_types: HashMap<
         String,
         graphql_parser::schema::ObjectType<'a, String>,
     >,
+    pub scalar_types: HashMap<
+        String,
+        graphql_parser::schema::ScalarType<'a, String>,
+    >,
+    pub union_types: HashMap<
+        String,
+        graphql_parser::schema::UnionType<'a, String>,
+    >,
     pub schema_def: graphql_parser::schema::SchemaDefinition<'a, String>,
 }
 impl<'a> SchemaInfo<'a> {
@@ -31,6 +47,7 @@ impl<'a> SchemaInfo<'a> {
         // just "leak" the source string here to give it 'static lifetime. This should be ok since
         // this stuff runs at compile time...so the "leak" only lasts as long as the macro
         // expansion.
+        let (schema_str, schema_extension_positions) = blank_out_schema_extensions(&schema_str);
         let schema_str_leaked = Box::leak(schema_str.into_boxed_str());
         let graphql_schema_doc: graphql_parser::schema::Document<'a, String> =
             match graphql_parser::parse_schema(schema_str_leaked) {
@@ -42,15 +59,37 @@ impl<'a> SchemaInfo<'a> {
             String,
             graphql_parser::schema::EnumType<'a, String>
         > = HashMap::new();
+        let mut input_obj_types: HashMap<
+            String,
+            graphql_parser::schema::InputObjectType<'a, String>
+        > = HashMap::new();
+        let mut interface_types: HashMap<
+            String,
+            graphql_parser::schema::InterfaceType<'a, String>
+        > = HashMap::new();
         let mut obj_types: HashMap<
             String,
             graphql_parser::schema::ObjectType<'a, String>
         > = HashMap::new();
+        let mut scalar_types: HashMap<
+            String,
+            graphql_parser::schema::ScalarType<'a, String>
+        > = HashMap::new();
+        let mut union_types: HashMap<
+            String,
+            graphql_parser::schema::UnionType<'a, String>
+        > = HashMap::new();
         let mut schema_def = None::<graphql_parser::schema::SchemaDefinition<'a, String>>;
+        let mut schema_extensions = vec![];
+        let mut type_extensions = vec![];
 
         for def in graphql_schema_doc.definitions {
             use graphql_parser::schema;
             match def {
+                schema::Definition::SchemaDefinition(def)
+                    if schema_extension_positions.contains(&def.position) => {
+                    schema_extensions.push(def);
+                },
                 schema::Definition::SchemaDefinition(def) => {
                     if let Some(prev_def) = schema_def {
                         return Err(CodegenError::MultipleSchemaDefinitions {
@@ -70,13 +109,25 @@ impl<'a> SchemaInfo<'a> {
                         });
                     }
                 },
-                schema::Definition::TypeDefinition(schema::TypeDefinition::InputObject(_inputobj_type)) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::TypeDefinition(schema::TypeDefinition::InputObject(inputobj_type)) => {
+                    let name = (&inputobj_type).name.clone();
+                    let pos = (&inputobj_type).position.clone();
+                    if let Err(err) = input_obj_types.try_insert(name, inputobj_type) {
+                        return Err(CodegenError::MultipleInputObjectTypeDefinitions {
+                            first: err.entry.get().position.clone(),
+                            second: pos,
+                        });
+                    }
                 },
-                schema::Definition::TypeDefinition(schema::TypeDefinition::Interface(_interface_type)) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Interface(interface_type)) => {
+                    let name = (&interface_type).name.clone();
+                    let pos = (&interface_type).position.clone();
+                    if let Err(err) = interface_types.try_insert(name, interface_type) {
+                        return Err(CodegenError::MultipleInterfaceTypeDefinitions {
+                            first: err.entry.get().position.clone(),
+                            second: pos,
+                        });
+                    }
                 },
                 schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj_type)) => {
                     let name = (&obj_type).name.clone();
@@ -88,33 +139,539 @@ impl<'a> SchemaInfo<'a> {
                         });
                     }
                 },
-                schema::Definition::TypeDefinition(schema::TypeDefinition::Scalar(_scalar_type)) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Scalar(scalar_type)) => {
+                    let name = (&scalar_type).name.clone();
+                    let pos = (&scalar_type).position.clone();
+                    if let Err(err) = scalar_types.try_insert(name, scalar_type) {
+                        return Err(CodegenError::MultipleScalarTypeDefinitions {
+                            first: err.entry.get().position.clone(),
+                            second: pos,
+                        });
+                    }
                 },
-                schema::Definition::TypeDefinition(schema::TypeDefinition::Union(_union_type)) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Union(union_type)) => {
+                    let name = (&union_type).name.clone();
+                    let pos = (&union_type).position.clone();
+                    if let Err(err) = union_types.try_insert(name, union_type) {
+                        return Err(CodegenError::MultipleUnionTypeDefinitions {
+                            first: err.entry.get().position.clone(),
+                            second: pos,
+                        });
+                    }
                 },
-                schema::Definition::TypeExtension(_) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::TypeExtension(type_ext) => {
+                    // Extensions may appear before the type they extend, so
+                    // hold on to them until all type definitions are collected.
+                    type_extensions.push(type_ext);
                 },
-                schema::Definition::DirectiveDefinition(_) => {
-                    // Switch these todo macros to a CodegenError variant
-                    todo!()
+                schema::Definition::DirectiveDefinition(directive_def) => {
+                    return Err(CodegenError::UnsupportedDefinition {
+                        kind: "directive",
+                        name: directive_def.name,
+                        pos: directive_def.position,
+                    });
                 },
             }
         }
 
-        if let Some(schema_def) = schema_def {
-            Ok(SchemaInfo {
-                enum_types,
-                obj_types,
-                schema_def,
-            })
+        let schema_def_is_inferred = schema_def.is_none();
+        let schema_def =
+            if let Some(schema_def) = schema_def {
+                schema_def
+            } else if let Some(query_type) = obj_types.get("Query") {
+                // Per the spec, a schema without a `schema {}` definition uses
+                // the object types with the default root type names (if they
+                // exist) as its root operation types. There's no definition to
+                // point at in the schema, so it's positioned at the Query type.
+                let default_root_type = |type_name: &str| {
+                    obj_types.get_key_value(type_name).map(|(name, _)| name.clone())
+                };
+                graphql_parser::schema::SchemaDefinition {
+                    position: query_type.position,
+                    directives: vec![],
+                    query: default_root_type("Query"),
+                    mutation: default_root_type("Mutation"),
+                    subscription: default_root_type("Subscription"),
+                }
+            } else if let Some(schema_ext) = schema_extensions.first() {
+                return Err(CodegenError::UndefinedExtendedType {
+                    name: String::from("schema"),
+                    pos: schema_ext.position,
+                });
+            } else {
+                return Err(CodegenError::NoSchemaDefinitionFound);
+            };
+
+        let mut schema_info = SchemaInfo {
+            enum_types,
+            input_obj_types,
+            interface_types,
+            obj_types,
+            scalar_types,
+            union_types,
+            schema_def,
+        };
+
+        for schema_ext in schema_extensions {
+            schema_info.apply_schema_extension(schema_ext, schema_def_is_inferred)?;
+        }
+        for type_ext in type_extensions {
+            schema_info.apply_type_extension(type_ext)?;
+        }
+
+        Ok(schema_info)
+    }
+
+    /**
+     * Folds the operation types from an `extend schema { ... }` definition
+     * into the schema definition. An extension may only add operation types
+     * that the schema doesn't already declare.
+     *
+     * When the schema definition was inferred (i.e. there's no `schema {}` in
+     * the schema), a conflicting operation type is reported at the object type
+     * it was inferred from.
+     */
+    fn apply_schema_extension(
+        &mut self,
+        schema_ext: graphql_parser::schema::SchemaDefinition<'a, String>,
+        schema_def_is_inferred: bool,
+    ) -> Result<(), CodegenError> {
+        let operations = [
+            ("query", &mut self.schema_def.query, schema_ext.query),
+            ("mutation", &mut self.schema_def.mutation, schema_ext.mutation),
+            ("subscription", &mut self.schema_def.subscription, schema_ext.subscription),
+        ];
+        for (operation_name, schema_operation, ext_operation) in operations {
+            if let Some(ext_operation) = ext_operation {
+                if let Some(operation_type_name) = schema_operation {
+                    let first = match self.obj_types.get(operation_type_name) {
+                        Some(operation_type) if schema_def_is_inferred => operation_type.position,
+                        _ => self.schema_def.position,
+                    };
+                    return Err(CodegenError::DuplicateExtensionMember {
+                        type_name: String::from("schema"),
+                        member_name: String::from(operation_name),
+                        first,
+                        second: schema_ext.position,
+                    });
+                }
+                let _ = schema_operation.insert(ext_operation);
+            }
+        }
+
+        self.schema_def.directives.extend(schema_ext.directives);
+        Ok(())
+    }
+
+    /**
+     * Folds an `extend <kind> Name { ... }` definition into the definition of
+     * the type it extends. Extensions can only add to a type (new fields,
+     * interfaces, enum values, union members, or directives) -- it's an error
+     * for an extension to redefine something the type already has.
+     */
+    fn apply_type_extension(
+        &mut self,
+        type_ext: graphql_parser::schema::TypeExtension<'a, String>,
+    ) -> Result<(), CodegenError> {
+        use graphql_parser::schema::TypeExtension;
+        match type_ext {
+            TypeExtension::Enum(enum_ext) => {
+                let enum_type = get_extended_type(&mut self.enum_types, &enum_ext.name, enum_ext.position)?;
+                for value in enum_ext.values {
+                    if let Some(prev_value) = enum_type.values.iter().find(|v| v.name == value.name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: enum_type.name.clone(),
+                            member_name: value.name,
+                            first: prev_value.position,
+                            second: value.position,
+                        });
+                    }
+                    enum_type.values.push(value);
+                }
+                enum_type.directives.extend(enum_ext.directives);
+            },
+
+            TypeExtension::InputObject(input_obj_ext) => {
+                let input_obj_type = get_extended_type(
+                    &mut self.input_obj_types,
+                    &input_obj_ext.name,
+                    input_obj_ext.position,
+                )?;
+                for field in input_obj_ext.fields {
+                    if let Some(prev_field) = input_obj_type.fields.iter().find(|f| f.name == field.name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: input_obj_type.name.clone(),
+                            member_name: field.name,
+                            first: prev_field.position,
+                            second: field.position,
+                        });
+                    }
+                    input_obj_type.fields.push(field);
+                }
+                input_obj_type.directives.extend(input_obj_ext.directives);
+            },
+
+            TypeExtension::Interface(interface_ext) => {
+                let interface_type = get_extended_type(
+                    &mut self.interface_types,
+                    &interface_ext.name,
+                    interface_ext.position,
+                )?;
+                for field in interface_ext.fields {
+                    if let Some(prev_field) = interface_type.fields.iter().find(|f| f.name == field.name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: interface_type.name.clone(),
+                            member_name: field.name,
+                            first: prev_field.position,
+                            second: field.position,
+                        });
+                    }
+                    interface_type.fields.push(field);
+                }
+                for interface_name in interface_ext.implements_interfaces {
+                    if interface_type.implements_interfaces.contains(&interface_name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: interface_type.name.clone(),
+                            member_name: interface_name,
+                            first: interface_type.position,
+                            second: interface_ext.position,
+                        });
+                    }
+                    interface_type.implements_interfaces.push(interface_name);
+                }
+                interface_type.directives.extend(interface_ext.directives);
+            },
+
+            TypeExtension::Object(obj_ext) => {
+                let obj_type = get_extended_type(&mut self.obj_types, &obj_ext.name, obj_ext.position)?;
+                for field in obj_ext.fields {
+                    if let Some(prev_field) = obj_type.fields.iter().find(|f| f.name == field.name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: obj_type.name.clone(),
+                            member_name: field.name,
+                            first: prev_field.position,
+                            second: field.position,
+                        });
+                    }
+                    obj_type.fields.push(field);
+                }
+                for interface_name in obj_ext.implements_interfaces {
+                    if obj_type.implements_interfaces.contains(&interface_name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: obj_type.name.clone(),
+                            member_name: interface_name,
+                            first: obj_type.position,
+                            second: obj_ext.position,
+                        });
+                    }
+                    obj_type.implements_interfaces.push(interface_name);
+                }
+                obj_type.directives.extend(obj_ext.directives);
+            },
+
+            TypeExtension::Scalar(scalar_ext) => {
+                let scalar_type = get_extended_type(
+                    &mut self.scalar_types,
+                    &scalar_ext.name,
+                    scalar_ext.position,
+                )?;
+                scalar_type.directives.extend(scalar_ext.directives);
+            },
+
+            TypeExtension::Union(union_ext) => {
+                let union_type = get_extended_type(
+                    &mut self.union_types,
+                    &union_ext.name,
+                    union_ext.position,
+                )?;
+                for member_name in union_ext.types {
+                    if union_type.types.contains(&member_name) {
+                        return Err(CodegenError::DuplicateExtensionMember {
+                            type_name: union_type.name.clone(),
+                            member_name,
+                            first: union_type.position,
+                            second: union_ext.position,
+                        });
+                    }
+                    union_type.types.push(member_name);
+                }
+                union_type.directives.extend(union_ext.directives);
+            },
+        }
+
+        Ok(())
+    }
+}
+
+fn get_extended_type<'m, T>(
+    types: &'m mut HashMap<String, T>,
+    name: &String,
+    pos: graphql_parser::Pos,
+) -> Result<&'m mut T, CodegenError> {
+    types.get_mut(name).ok_or_else(|| CodegenError::UndefinedExtendedType {
+        name: name.clone(),
+        pos,
+    })
+}
+
+/**
+ * graphql_parser doesn't support `extend schema { ... }` (it only knows how to
+ * parse type extensions), so we handle these ourselves: Every `extend` keyword
+ * that introduces a schema extension is replaced with whitespace so that
+ * graphql_parser sees a regular `schema { ... }` definition instead.
+ *
+ * Blanking out the keyword (rather than removing it) keeps every position in
+ * the document unchanged. The positions of the `schema` keywords that
+ * followed an `extend` are returned so that the parsed SchemaDefinitions can be
+ * told apart from the real one and folded in as extensions.
+ */
+fn blank_out_schema_extensions(schema_str: &str) -> (String, HashSet<graphql_parser::Pos>) {
+    let mut result = String::with_capacity(schema_str.len());
+    let mut extension_positions = HashSet::new();
+
+    // Position (and offset into `result`) of an `extend` keyword that has only
+    // been followed by insignificant tokens (whitespace, commas, comments) so
+    // far.
+    let mut pending_extend = None::<usize>;
+
+    let mut line = 1;
+    let mut column = 1;
+    let mut chars = schema_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        let pos = graphql_parser::Pos { line, column };
+        let mut advance = |c: char| {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        };
+
+        if c == '#' {
+            // Comments run until the end of the line
+            result.push(c);
+            advance(c);
+            while let Some(&c) = chars.peek() {
+                if c == '\n' {
+                    break;
+                }
+                result.push(c);
+                advance(c);
+                chars.next();
+            }
+        } else if c == '"' {
+            // Strings (including """block strings""") are copied verbatim
+            pending_extend = None;
+            result.push(c);
+            advance(c);
+            let mut quote_count = 1;
+            while let Some(&c) = chars.peek() {
+                if c == '"' {
+                    quote_count += 1;
+                } else {
+                    break;
+                }
+                result.push(c);
+                advance(c);
+                chars.next();
+            }
+            let is_block_string = quote_count == 3;
+            if quote_count == 2 {
+                // Empty string
+                continue;
+            }
+
+            let mut consecutive_quotes = 0;
+            while let Some(c) = chars.next() {
+                result.push(c);
+                advance(c);
+                if c == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        result.push(escaped);
+                        advance(escaped);
+                    }
+                    consecutive_quotes = 0;
+                } else if c == '"' {
+                    consecutive_quotes += 1;
+                    if !is_block_string || consecutive_quotes == 3 {
+                        break;
+                    }
+                } else {
+                    consecutive_quotes = 0;
+                }
+            }
+        } else if c == '_' || c.is_ascii_alphabetic() {
+            let name_start = result.len();
+            let mut name = String::from(c);
+            advance(c);
+            while let Some(&c) = chars.peek() {
+                if c == '_' || c.is_ascii_alphanumeric() {
+                    name.push(c);
+                    advance(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            if name == "schema" {
+                if let Some(extend_start) = pending_extend {
+                    result.replace_range(extend_start..extend_start + "extend".len(), "      ");
+                    extension_positions.insert(pos);
+                }
+            }
+            pending_extend = if name == "extend" { Some(name_start) } else { None };
+            result.push_str(&name);
         } else {
-            Err(CodegenError::NoSchemaDefinitionFound)
+            if !(c.is_whitespace() || c == ',' || c == '\u{feff}') {
+                pending_extend = None;
+            }
+            result.push(c);
+            advance(c);
+        }
+    }
+
+    (result, extension_positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize) -> graphql_parser::Pos {
+        graphql_parser::Pos { line, column }
+    }
+
+    #[test]
+    fn blanks_out_extend_before_schema() {
+        let schema = "schema { query: Query }\nextend schema { mutation: Mutation }\n";
+        let (blanked, positions) = blank_out_schema_extensions(schema);
+        assert_eq!(blanked, "schema { query: Query }\n       schema { mutation: Mutation }\n");
+        assert_eq!(positions, HashSet::from([pos(2, 8)]));
+    }
+
+    #[test]
+    fn blanks_out_extend_separated_from_schema_by_a_comment() {
+        let schema = "extend # more operations\n  , schema { mutation: Mutation }";
+        let (blanked, positions) = blank_out_schema_extensions(schema);
+        assert_eq!(blanked, "       # more operations\n  , schema { mutation: Mutation }");
+        assert_eq!(positions, HashSet::from([pos(2, 5)]));
+    }
+
+    #[test]
+    fn leaves_strings_and_comments_alone() {
+        let schema = concat!(
+            "# extend schema { mutation: Mutation }\n",
+            "\"\"\"\n",
+            "Not an extension: extend schema { mutation: Mutation }\n",
+            "\"\"\"\n",
+            "type Query {\n",
+            "  \"escaped \\\" extend schema\" a: String\n",
+            "  \"\" b: String\n",
+            "}\n",
+        );
+        let (blanked, positions) = blank_out_schema_extensions(schema);
+        assert_eq!(blanked, schema);
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn doesnt_treat_other_names_as_extend() {
+        let schema = "type extendschema { a: String }\ntype Query { extend: String schema: String }";
+        let (blanked, positions) = blank_out_schema_extensions(schema);
+        assert_eq!(blanked, schema);
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn keeps_positions_unchanged() {
+        let schema = concat!(
+            "\u{feff}type Query { a: String }\n",
+            "extend schema { mutation: Writes }\n",
+            "type Writes {\n",
+            "  \"\"\"\n",
+            "  multi-line\n",
+            "  \"\"\"\n",
+            "  b: String\n",
+            "}\n",
+        );
+        let (blanked, _) = blank_out_schema_extensions(schema);
+        assert_eq!(blanked.len(), schema.len());
+        assert_eq!(blanked.lines().count(), schema.lines().count());
+
+        let schema_info = match SchemaInfo::parse(String::from(schema)) {
+            Ok(schema_info) => schema_info,
+            Err(e) => panic!("unexpected error: {:?}", e),
+        };
+        assert_eq!(schema_info.schema_def.mutation.as_deref(), Some("Writes"));
+        let mutation_type = &schema_info.obj_types["Writes"];
+        assert_eq!(mutation_type.position, pos(3, 1));
+        // A field with a description is positioned at the start of its
+        // description
+        assert_eq!(mutation_type.fields[0].position, pos(4, 3));
+    }
+
+    #[test]
+    fn rejects_duplicate_extension_operation_type() {
+        let schema = concat!(
+            "schema { query: Query }\n",
+            "extend schema { query: OtherQuery }\n",
+            "type Query { a: String }\n",
+            "type OtherQuery { a: String }\n",
+        );
+        match SchemaInfo::parse(String::from(schema)) {
+            Err(CodegenError::DuplicateExtensionMember { type_name, member_name, first, second }) => {
+                assert_eq!(type_name, "schema");
+                assert_eq!(member_name, "query");
+                assert_eq!(first, pos(1, 1));
+                assert_eq!(second, pos(2, 8));
+            },
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn reports_inferred_operation_type_conflicts_at_the_inferred_type() {
+        let schema = concat!(
+            "# Root types\n",
+            "type Query { a: String }\n",
+            "extend schema { mutation: Writes }\n",
+            "type Mutation { b: String }\n",
+            "type Writes { c: String }\n",
+        );
+        match SchemaInfo::parse(String::from(schema)) {
+            Err(CodegenError::DuplicateExtensionMember { member_name, first, second, .. }) => {
+                assert_eq!(member_name, "mutation");
+                assert_eq!(first, pos(4, 1));
+                assert_eq!(second, pos(3, 8));
+            },
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn positions_inferred_schema_at_query_type() {
+        let schema = "# Root types\n\ntype Query { a: String }\n";
+        match SchemaInfo::parse(String::from(schema)) {
+            Ok(schema_info) => assert_eq!(schema_info.schema_def.position, pos(3, 1)),
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn rejects_extension_of_undefined_type() {
+        let schema = "type Query { a: String }\nextend type User { b: String }\n";
+        match SchemaInfo::parse(String::from(schema)) {
+            Err(CodegenError::UndefinedExtendedType { name, pos: ext_pos }) => {
+                assert_eq!(name, "User");
+                // graphql_parser positions type extensions at the `type` keyword
+                assert_eq!(ext_pos, pos(2, 8));
+            },
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("expected an error"),
         }
     }
 }
@@ -3,8 +3,8 @@ use super::Context;
 
 pub struct User;
 impl User {
-    pub async fn id(&self, _ctx: &Context) -> String {
-        "sadf".to_string()
+    pub async fn id(&self, _ctx: &Context) -> Option<juniper::ID> {
+        Some(juniper::ID::new("user:jeffmo"))
     }
 }
 /*
@@ -15,8 +15,11 @@ impl UserFieldResolvers for User {
     }
 }
 
-// TODO: Make this work (see docblock in the impl of the proc_macro)
-#[juniper_schema::field_resolvers]
+// Or, equivalently (see docblock on the proc_macro):
+#[juniper_schema::field_resolvers(schema_module=super)]
 impl User {
+    async fn resolve_id(&self, _ctx: &Context) -> Option<juniper::ID> {
+        Some(juniper::ID::new("user:jeffmo"))
+    }
 }
 */
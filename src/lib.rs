@@ -1,7 +1,8 @@
 use juniper_schema_lib::CodegenFromFile;
+use juniper_schema_lib::FieldResolversArgs;
+use juniper_schema_lib::ImplToTraitMapper;
 use juniper_schema_lib::SchemaFromFile2;
 use juniper_schema_lib::SchemaFromFile3;
-//use juniper_schema_lib::ImplToTraitMapper;
 
 #[proc_macro]
 pub fn from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -61,44 +62,52 @@ pub fn from_file3(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
 }
 
+/**
+ * Implements the `<Type>FieldResolvers` trait that `from_file!()` generates
+ * for a GraphQL object type using the resolvers in an inherent impl block:
+ *
+ * ```text
+ * #[field_resolvers(schema_module=super::schema)]
+ * impl MyGraphqlTypeStruct {
+ *     [...]
+ * }
+ * ```
+ *
+ * translates to:
+ *
+ * ```text
+ * #[async_trait::async_trait]
+ * impl super::schema::MyGraphqlTypeStructFieldResolvers for MyGraphqlTypeStruct {
+ *     [...]
+ * }
+ * ```
+ *
+ * `schema_module` is the path of the module that `from_file!()` is invoked in
+ * (which is where the resolver traits are generated). If the Rust type isn't
+ * named the same as the GraphQL type, pass `graphql_type=...` as well.
+ */
 #[proc_macro_attribute]
 pub fn field_resolvers(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    /*
-     * !!!! TODO: Given the following:
-     *
-     *     #[field_resolvers(schema_module=super::schema)]
-     *     impl MyGraphqlTypeStruct {
-     *         [...]
-     *     }
-     *
-     * translate to:
-     *
-     *     impl super::schema::MyGraphqlTypeStructFieldResolvers for MyGraphqlTypeStruct {
-     *         [...]
-     *      }
-     *
-     * If schema_module attr arg is missing, emit a compile error explaining
-     * what the arg is and why it is needed.
-     */
+    let args = match syn::parse::<FieldResolversArgs>(attr) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
-    input
-}
+    let item_impl = match syn::parse::<syn::ItemImpl>(input) {
+        Ok(item_impl) => item_impl,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
-/*
-#[proc_macro_attribute]
-pub fn ____juniper_obj_impl_mapper(
-    _attr: proc_macro::TokenStream,
-    input: proc_macro::TokenStream,
-) -> proc_macro::TokenStream {
-    return input
-    /*
-    match syn::parse::<ImplToTraitMapper>(input) {
-        Ok(mapper) => mapper.to_tokens().into(),
+    let mapper = match ImplToTraitMapper::new(args, item_impl) {
+        Ok(mapper) => mapper,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    match mapper.to_tokens() {
+        Ok(tokens) => tokens.into(),
         Err(e) => e.to_compile_error().into(),
     }
-    */
 }
-*/